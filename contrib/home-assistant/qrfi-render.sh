@@ -0,0 +1,13 @@
+#!/bin/sh
+# Usage: qrfi-render.sh SSID_BASE64 PASSWORD_BASE64 OUTPUT [WPA|SAE|WEP]
+#
+# Home Assistant passes the SSID and password base64-encoded, so quotes, `$`, and backticks in them survive its
+# argument splitting. qrfi reads them on stdin in interactive mode rather than on its command line, where every
+# local user could see the password.
+set -eu
+
+ssid=$(printf '%s' "$1" | base64 -d)
+password=$(printf '%s' "$2" | base64 -d)
+mkdir -p "$(dirname "$3")"
+printf '%s\n%s\n%s\nn\n' "$ssid" "${4:-WPA}" "$password" | qrfi --interactive --format svg > "$3.tmp" 2>/dev/null
+mv "$3.tmp" "$3"
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use qrfi::{mecard_unescape, Wifi};
+
+fuzz_target!(|data: &str| {
+    let _ = mecard_unescape(data);
+    if let Ok(wifi) = Wifi::from_mecard(data) {
+        // Whatever parses must come back unchanged from its own payload.
+        let mecard = wifi.to_mecard();
+        let reparsed = Wifi::from_mecard(&mecard).expect("to_mecard output should parse");
+        assert_eq!(reparsed.to_mecard(), mecard);
+    }
+});
@@ -0,0 +1,12 @@
+use std::fs;
+use std::path::Path;
+
+fn main() -> std::io::Result<()> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus/from_mecard");
+    fs::create_dir_all(&dir)?;
+    for (i, input) in qrfi::PARSER_CORPUS.iter().enumerate() {
+        fs::write(dir.join(format!("seed-{:02}", i)), input)?;
+    }
+    println!("Wrote {} seeds to {}", qrfi::PARSER_CORPUS.len(), dir.display());
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Lowercase hex encoding of a SHA-256 digest.
+pub fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Passes writes through to `inner` while hashing them with SHA-256.
+pub struct HashWriter<W> {
+    inner: W,
+    hasher: Sha256,
+}
+impl<W: Write> HashWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, hasher: Sha256::new() }
+    }
+
+    /// Flushes the inner writer and returns the hex digest of everything written.
+    pub fn finish(mut self) -> io::Result<String> {
+        self.inner.flush()?;
+        Ok(hex(&self.hasher.finalize()))
+    }
+}
+impl<W: Write> Write for HashWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// One line of the audit log. The password is never recorded, not even as a hash.
+#[derive(Serialize)]
+struct Entry<'a> {
+    timestamp: String,
+    user: String,
+    ssid: &'a str,
+    artifact: &'a str,
+    /// `None` for artifacts derived from the password, such as the code of a secured network or a script with the
+    /// password in plain text, whose unsalted hash would let anyone holding the log test password guesses offline.
+    sha256: Option<&'a str>,
+}
+
+/// Formats a time as an RFC 3339 UTC timestamp with second precision.
+fn rfc3339(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, rem) = (secs / 86400, secs % 86400);
+    // Civil-from-days conversion for the proleptic Gregorian calendar.
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, rem / 3600, rem % 3600 / 60, rem % 60
+    )
+}
+
+/// Appends a JSON line describing a generated artifact to the audit log.
+pub fn append(log: &Path, ssid: &str, artifact: &str, sha256: Option<&str>) -> io::Result<()> {
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string());
+    let entry = Entry { timestamp: rfc3339(SystemTime::now()), user, ssid, artifact, sha256 };
+    let mut line = serde_json::to_string(&entry)?;
+    line.push('\n');
+    OpenOptions::new().create(true).append(true).open(log)?.write_all(line.as_bytes())
+}
@@ -0,0 +1,78 @@
+use qrfi::{AuthType, Wifi};
+use serde::Deserialize;
+use std::sync::LazyLock;
+
+/// Payload properties that compatibility rules key on.
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+enum Condition {
+    Hidden,
+    Wep,
+    EscapedSsid,
+    EscapedPassword,
+    QuotedSsid,
+    QuotedPassword,
+    HexSsid,
+    NonAsciiSsid,
+    RawKey,
+    Eap,
+    Sae,
+    TransitionDisable,
+    Owe,
+}
+
+/// A known caveat of some readers, loaded from `compat.toml`.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    when: Condition,
+    pub readers: String,
+    pub caveat: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct Rules {
+    rule: Vec<Rule>,
+}
+
+/// The rules table, parsed on first use.
+static RULES: LazyLock<Rules> =
+    LazyLock::new(|| toml::from_str(include_str!("compat.toml")).expect("compat.toml is a valid rules table"));
+
+/// The caveats that apply to the payload of `wifi`, in the order of the rules table.
+pub fn caveats(wifi: &Wifi, hex_ssid: bool, sae_as_wpa: bool) -> Vec<&'static Rule> {
+    let ssid = wifi.ssid().as_str().filter(|_| !hex_ssid);
+    let escaped_ssid = wifi.ssid().escape();
+    let password = wifi.password();
+    let raw_password = password.expose_secret().unwrap_or_default();
+    let escaped_password = password.escape();
+    let applies = |condition: Condition| match condition {
+        Condition::Hidden => wifi.hidden(),
+        Condition::Wep => password.auth_type() == AuthType::Wep,
+        Condition::EscapedSsid => ssid.is_some() && escaped_ssid.contains('\\'),
+        Condition::EscapedPassword => escaped_password.contains('\\'),
+        Condition::QuotedSsid => ssid.is_some_and(|s| escaped_ssid.len() == s.len() + 2 && escaped_ssid.starts_with('"')),
+        Condition::QuotedPassword => escaped_password.len() == raw_password.len() + 2 && escaped_password.starts_with('"'),
+        Condition::HexSsid => ssid.is_none(),
+        Condition::NonAsciiSsid => ssid.is_some_and(|s| !s.is_ascii()),
+        Condition::RawKey => password.auth_type() == AuthType::Wpa && raw_password.len() == 64,
+        Condition::Eap => password.auth_type() == AuthType::Wpa2Eap,
+        Condition::Owe => password.auth_type() == AuthType::Owe,
+        Condition::Sae => password.auth_type() == AuthType::Sae && !sae_as_wpa,
+        Condition::TransitionDisable => {
+            wifi.transition_disable() && matches!(password.auth_type(), AuthType::Wpa | AuthType::Sae)
+        }
+    };
+    RULES.rule.iter().filter(|rule| applies(rule.when)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_rules_parse() {
+        assert!(!LazyLock::force(&RULES).rule.is_empty());
+    }
+}
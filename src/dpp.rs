@@ -0,0 +1,183 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// A Wi-Fi Easy Connect (DPP) bootstrapping URI, which a configurator scans to provision a headless device.
+///
+/// Unlike a `WIFI:` code it holds no credentials: the device's public key is enough for the configurator to send
+/// it the network settings over an authenticated exchange.
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{Channel, Dpp};
+///
+/// let dpp = Dpp::new("MDkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDIgADURzxmttZoIRIPWGoQMV00XHWCAQIhXruVWOz0NjlkIA=")
+///     .unwrap()
+///     .with_channels(vec![Channel::new(81, 1).unwrap(), Channel::new(115, 36).unwrap()])
+///     .with_mac([0x52, 0x54, 0x00, 0x58, 0x28, 0xe5])
+///     .with_version(2);
+/// assert_eq!(
+///     dpp.to_uri(),
+///     "DPP:C:81/1,115/36;M:5254005828e5;V:2;K:MDkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDIgADURzxmttZoIRIPWGoQMV00XHWCAQIhXruVWOz0NjlkIA=;;"
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Dpp {
+    public_key: String,
+    channels: Vec<Channel>,
+    mac: Option<[u8; 6]>,
+    info: Option<String>,
+    version: Option<u8>,
+}
+impl Dpp {
+    /// Takes the device's public key as base64 of its DER `SubjectPublicKeyInfo`, as `K:` carries it.
+    pub fn new(public_key: impl Into<String>) -> Result<Self, DppError> {
+        let public_key = public_key.into();
+        let data = public_key.trim_end_matches('=');
+        let is_base64 = data.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/'));
+        if data.is_empty() || !is_base64 || public_key.len() % 4 != 0 || public_key.len() - data.len() > 2 {
+            return Err(DppError::InvalidPublicKey);
+        }
+        Ok(Self { public_key, channels: Vec::new(), mac: None, info: None, version: None })
+    }
+
+    /// Sets the channels the device listens on for the exchange, in the order it prefers them.
+    pub fn with_channels(mut self, channels: Vec<Channel>) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Sets the MAC address of the device's Wi-Fi interface.
+    pub fn with_mac(mut self, mac: [u8; 6]) -> Self {
+        self.mac = Some(mac);
+        self
+    }
+
+    /// Sets a description the configurator may show, such as a model name; an empty string means none.
+    ///
+    /// # Errors
+    ///
+    /// [`DppError::InvalidInfo`] unless the text is printable ASCII without `;`.
+    pub fn with_info(mut self, info: impl Into<String>) -> Result<Self, DppError> {
+        let info = info.into();
+        if !info.bytes().all(|b| matches!(b, 0x20..=0x7e) && b != b';') {
+            return Err(DppError::InvalidInfo);
+        }
+        self.info = Some(info).filter(|s| !s.is_empty());
+        Ok(self)
+    }
+
+    /// Sets the DPP protocol version the device supports, `2` for Easy Connect R2.
+    pub fn with_version(mut self, version: u8) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    pub fn channels(&self) -> &[Channel] {
+        &self.channels
+    }
+
+    pub fn mac(&self) -> Option<[u8; 6]> {
+        self.mac
+    }
+
+    pub fn info(&self) -> Option<&str> {
+        self.info.as_deref()
+    }
+
+    pub fn version(&self) -> Option<u8> {
+        self.version
+    }
+
+    pub fn to_uri(&self) -> String {
+        self.to_string()
+    }
+}
+impl core::fmt::Display for Dpp {
+    /// Formats the `DPP:` URI with the fields in the order of the Easy Connect specification.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("DPP:")?;
+        if !self.channels.is_empty() {
+            f.write_str("C:")?;
+            for (i, channel) in self.channels.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "{}", channel)?;
+            }
+            f.write_str(";")?;
+        }
+        if let Some(mac) = self.mac {
+            f.write_str("M:")?;
+            for byte in mac {
+                write!(f, "{:02x}", byte)?;
+            }
+            f.write_str(";")?;
+        }
+        if let Some(info) = &self.info {
+            write!(f, "I:{};", info)?;
+        }
+        if let Some(version) = self.version {
+            write!(f, "V:{};", version)?;
+        }
+        write!(f, "K:{};;", self.public_key)
+    }
+}
+
+/// A global operating class and a channel number in it, written as `class/channel`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Channel {
+    class: u8,
+    channel: u8,
+}
+impl Channel {
+    /// Takes an operating class from Annex E of IEEE 802.11, such as 81 for 2.4 GHz or 115 for 5 GHz, and a
+    /// channel number, neither of which may be 0.
+    pub fn new(class: u8, channel: u8) -> Result<Self, DppError> {
+        if class == 0 || channel == 0 {
+            return Err(DppError::InvalidChannel { class, channel });
+        }
+        Ok(Self { class, channel })
+    }
+
+    pub fn class(&self) -> u8 {
+        self.class
+    }
+
+    pub fn channel(&self) -> u8 {
+        self.channel
+    }
+}
+impl core::fmt::Display for Channel {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}/{}", self.class, self.channel)
+    }
+}
+
+/// Reasons the fields of a [`Dpp`] URI fail validation.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DppError {
+    /// The public key is not padded base64.
+    InvalidPublicKey,
+    /// The operating class or channel is 0.
+    InvalidChannel { class: u8, channel: u8 },
+    /// The information text has characters other than printable ASCII, or a `;`.
+    InvalidInfo,
+}
+impl core::fmt::Display for DppError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            DppError::InvalidPublicKey => write!(f, "DPP public key must be base64 of a DER SubjectPublicKeyInfo."),
+            DppError::InvalidChannel { class, channel } => write!(
+                f,
+                "DPP channel {}/{} is invalid; neither the operating class nor the channel may be 0.", class, channel
+            ),
+            DppError::InvalidInfo => write!(f, "DPP information must be printable ASCII without ';'."),
+        }
+    }
+}
+impl core::error::Error for DppError {}
@@ -0,0 +1,191 @@
+use alloc::string::{String, ToString};
+
+use crate::error::ParseError;
+
+/// 802.1X settings of a WPA2-Enterprise (`WPA2-EAP`) network, written to the `E:`, `PH2:`, `I:`, and `A:` fields
+/// Android reads.
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{EapMethod, Enterprise, Phase2, WifiBuilder};
+///
+/// let enterprise = Enterprise::new(EapMethod::Peap).with_phase2(Phase2::Mschapv2).with_identity("alice");
+/// let wifi = WifiBuilder::new("campus").eap(enterprise, Some("PASSWORD".to_string())).build().unwrap();
+/// assert_eq!(wifi.to_mecard(), "WIFI:S:campus;T:WPA2-EAP;P:PASSWORD;E:PEAP;PH2:MSCHAPV2;I:alice;H:false;;");
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Enterprise {
+    method: EapMethod,
+    phase2: Option<Phase2>,
+    identity: Option<String>,
+    anonymous_identity: Option<String>,
+}
+impl Enterprise {
+    pub fn new(method: EapMethod) -> Self {
+        Self { method, phase2: None, identity: None, anonymous_identity: None }
+    }
+
+    /// Sets the inner authentication of tunneled methods such as PEAP and TTLS.
+    pub fn with_phase2(mut self, phase2: Phase2) -> Self {
+        self.phase2 = Some(phase2);
+        self
+    }
+
+    /// Sets the user name; an empty string means none.
+    pub fn with_identity(mut self, identity: impl Into<String>) -> Self {
+        self.identity = Some(identity.into()).filter(|s| !s.is_empty());
+        self
+    }
+
+    /// Sets the outer identity sent in the clear before the tunnel is set up; an empty string means none.
+    pub fn with_anonymous_identity(mut self, identity: impl Into<String>) -> Self {
+        self.anonymous_identity = Some(identity.into()).filter(|s| !s.is_empty());
+        self
+    }
+
+    pub fn method(&self) -> EapMethod {
+        self.method
+    }
+
+    pub fn phase2(&self) -> Option<Phase2> {
+        self.phase2
+    }
+
+    pub fn identity(&self) -> Option<&str> {
+        self.identity.as_deref()
+    }
+
+    pub fn anonymous_identity(&self) -> Option<&str> {
+        self.anonymous_identity.as_deref()
+    }
+
+    /// Writes the fields after `P:`, each preceded by its `;`.
+    pub(crate) fn write_fields<W: core::fmt::Write + ?Sized>(&self, out: &mut W) -> core::fmt::Result {
+        write!(out, ";E:{}", self.method)?;
+        if let Some(phase2) = self.phase2 {
+            write!(out, ";PH2:{}", phase2)?;
+        }
+        if let Some(identity) = &self.identity {
+            out.write_str(";I:")?;
+            crate::write_escaped(out, identity)?;
+        }
+        if let Some(identity) = &self.anonymous_identity {
+            out.write_str(";A:")?;
+            crate::write_escaped(out, identity)?;
+        }
+        Ok(())
+    }
+}
+
+/// EAP methods Android accepts in the `E:` field.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EapMethod {
+    /// Protected EAP, a TLS tunnel around a password exchange.
+    #[cfg_attr(feature = "cli", value(name = "PEAP"))]
+    #[cfg_attr(feature = "serde", serde(rename = "PEAP"))]
+    Peap,
+    /// Client certificate authentication.
+    #[cfg_attr(feature = "cli", value(name = "TLS"))]
+    #[cfg_attr(feature = "serde", serde(rename = "TLS"))]
+    Tls,
+    /// Tunneled TLS, a TLS tunnel around a legacy password exchange.
+    #[cfg_attr(feature = "cli", value(name = "TTLS"))]
+    #[cfg_attr(feature = "serde", serde(rename = "TTLS"))]
+    Ttls,
+    /// Password authentication without a tunnel.
+    #[cfg_attr(feature = "cli", value(name = "PWD"))]
+    #[cfg_attr(feature = "serde", serde(rename = "PWD"))]
+    Pwd,
+    /// SIM card authentication.
+    #[cfg_attr(feature = "cli", value(name = "SIM"))]
+    #[cfg_attr(feature = "serde", serde(rename = "SIM"))]
+    Sim,
+    /// USIM card authentication.
+    #[cfg_attr(feature = "cli", value(name = "AKA"))]
+    #[cfg_attr(feature = "serde", serde(rename = "AKA"))]
+    Aka,
+    /// USIM card authentication with the improved key derivation of EAP-AKA'.
+    #[cfg_attr(feature = "cli", value(name = "AKA'"))]
+    #[cfg_attr(feature = "serde", serde(rename = "AKA'"))]
+    AkaPrime,
+}
+impl core::fmt::Display for EapMethod {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            EapMethod::Peap => "PEAP",
+            EapMethod::Tls => "TLS",
+            EapMethod::Ttls => "TTLS",
+            EapMethod::Pwd => "PWD",
+            EapMethod::Sim => "SIM",
+            EapMethod::Aka => "AKA",
+            EapMethod::AkaPrime => "AKA'",
+        })
+    }
+}
+impl core::str::FromStr for EapMethod {
+    type Err = ParseError;
+
+    /// Parses an EAP method, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "PEAP" => Ok(EapMethod::Peap),
+            "TLS" => Ok(EapMethod::Tls),
+            "TTLS" => Ok(EapMethod::Ttls),
+            "PWD" => Ok(EapMethod::Pwd),
+            "SIM" => Ok(EapMethod::Sim),
+            "AKA" => Ok(EapMethod::Aka),
+            "AKA'" => Ok(EapMethod::AkaPrime),
+            _ => Err(ParseError::UnknownEapMethod(s.to_string())),
+        }
+    }
+}
+
+/// Inner authentication methods Android accepts in the `PH2:` field.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Phase2 {
+    /// Plaintext password inside the tunnel.
+    #[cfg_attr(feature = "cli", value(name = "PAP"))]
+    #[cfg_attr(feature = "serde", serde(rename = "PAP"))]
+    Pap,
+    /// Microsoft CHAP.
+    #[cfg_attr(feature = "cli", value(name = "MSCHAP"))]
+    #[cfg_attr(feature = "serde", serde(rename = "MSCHAP"))]
+    Mschap,
+    /// Microsoft CHAP version 2, the usual choice for PEAP.
+    #[cfg_attr(feature = "cli", value(name = "MSCHAPV2"))]
+    #[cfg_attr(feature = "serde", serde(rename = "MSCHAPV2"))]
+    Mschapv2,
+    /// Generic Token Card, for one-time passwords.
+    #[cfg_attr(feature = "cli", value(name = "GTC"))]
+    #[cfg_attr(feature = "serde", serde(rename = "GTC"))]
+    Gtc,
+}
+impl core::fmt::Display for Phase2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            Phase2::Pap => "PAP",
+            Phase2::Mschap => "MSCHAP",
+            Phase2::Mschapv2 => "MSCHAPV2",
+            Phase2::Gtc => "GTC",
+        })
+    }
+}
+impl core::str::FromStr for Phase2 {
+    type Err = ParseError;
+
+    /// Parses a phase 2 method, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "PAP" => Ok(Phase2::Pap),
+            "MSCHAP" => Ok(Phase2::Mschap),
+            "MSCHAPV2" => Ok(Phase2::Mschapv2),
+            "GTC" => Ok(Phase2::Gtc),
+            _ => Err(ParseError::UnknownPhase2(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use alloc::string::String;
+
+use crate::ValidationPolicy;
+
+/// Reasons a Wi-Fi configuration fails validation.
+///
+/// The `Display` output is a human-readable message suitable for the CLI.
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{Ssid, ValidationError};
+///
+/// let err = Ssid::new("".to_string()).err().unwrap();
+/// assert_eq!(err, ValidationError::EmptySsid);
+/// assert_eq!(err.to_string(), "SSID cannot be empty.");
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ValidationError {
+    /// The SSID is empty.
+    EmptySsid,
+    /// The SSID is longer than 32 bytes.
+    SsidTooLong { bytes: usize },
+    /// A password was given for an open (`nopass` or `OWE`) network.
+    UnexpectedPassword,
+    /// The WPA passphrase is neither 8-63 characters nor 64 hex digits. The characters must be printable ASCII
+    /// unless `policy` is [`ValidationPolicy::Lenient`].
+    InvalidWpaPassphrase { bytes: usize, policy: ValidationPolicy },
+    /// The WEP key has none of the lengths `policy` accepts: 5 or 13 characters or 10 or 26 hex digits, and under
+    /// [`ValidationPolicy::Lenient`] also 16 or 29 characters or 32 or 58 hex digits.
+    InvalidWepKey { bytes: usize, policy: ValidationPolicy },
+    /// An SAE (WPA3) network has no password.
+    EmptySaePassword,
+    /// A `WPA2-EAP` network has no EAP method.
+    MissingEapMethod,
+    /// EAP settings were given for a network that is not `WPA2-EAP`.
+    UnexpectedEapSettings,
+}
+impl core::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            ValidationError::EmptySsid => write!(f, "SSID cannot be empty."),
+            ValidationError::SsidTooLong { bytes } => write!(
+                f,
+                "SSID is too long ({} bytes). It must be between 1 and 32 bytes.", bytes
+            ),
+            ValidationError::UnexpectedPassword => write!(f, "Password should not be provided for open networks ('nopass' or 'OWE')."),
+            ValidationError::InvalidWpaPassphrase { policy: ValidationPolicy::Lenient, .. } => write!(
+                f,
+                "WPA passphrase must be 8-63 bytes of UTF-8, or 64 hex digits."
+            ),
+            ValidationError::InvalidWpaPassphrase { .. } => write!(
+                f,
+                "WPA passphrase must be 8-63 printable ASCII characters, or 64 hex digits."
+            ),
+            ValidationError::InvalidWepKey { policy: ValidationPolicy::Lenient, .. } => write!(
+                f,
+                "WEP password must be 5, 13, 16, or 29 characters, or 10, 26, 32, or 58 hex digits."
+            ),
+            ValidationError::InvalidWepKey { .. } => write!(
+                f,
+                "WEP password must be 5 or 13 characters, or 10 or 26 hex digits."
+            ),
+            ValidationError::EmptySaePassword => write!(f, "SAE password cannot be empty."),
+            ValidationError::MissingEapMethod => write!(f, "WPA2-EAP networks need an EAP method."),
+            ValidationError::UnexpectedEapSettings => write!(f, "EAP settings are only allowed for WPA2-EAP networks."),
+        }
+    }
+}
+impl core::error::Error for ValidationError {}
+
+/// Reasons a `WIFI:` string cannot be parsed.
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{ParseError, ValidationError, Wifi};
+///
+/// assert_eq!(Wifi::from_mecard("MECARD:N:Alice;;").err(), Some(ParseError::MissingPrefix));
+/// assert_eq!(
+///     Wifi::from_mecard("WIFI:S:;;").err(),
+///     Some(ParseError::Invalid(ValidationError::EmptySsid))
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseError {
+    /// The string is longer than [`Wifi::MAX_MECARD_LEN`](crate::Wifi::MAX_MECARD_LEN) bytes.
+    TooLong { bytes: usize },
+    /// The string does not start with `WIFI:`.
+    MissingPrefix,
+    /// There is no `S:` field.
+    MissingSsid,
+    /// A field has no `:` separating its name from its value.
+    MalformedField(String),
+    /// A value ends with a backslash that escapes nothing.
+    DanglingEscape,
+    /// The authentication type is not one of the names `AuthType` accepts.
+    UnknownAuthType(String),
+    /// The `E:` field is not one of the names `EapMethod` accepts.
+    UnknownEapMethod(String),
+    /// The `PH2:` field is not one of the names `Phase2` accepts.
+    UnknownPhase2(String),
+    /// The `H:` field is not `true` or `false`.
+    InvalidHidden(String),
+    /// The `R:` field is not a hex bitmap.
+    InvalidTransitionDisable(String),
+    /// The fields parsed but do not describe a valid network.
+    Invalid(ValidationError),
+}
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            ParseError::TooLong { bytes } => write!(
+                f,
+                "Wi-Fi payload is too long ({} bytes). No QR code holds more than {} bytes.", bytes, crate::Wifi::MAX_MECARD_LEN
+            ),
+            ParseError::MissingPrefix => write!(f, "Wi-Fi payload must start with \"WIFI:\"."),
+            ParseError::MissingSsid => write!(f, "Wi-Fi payload has no S: field."),
+            ParseError::MalformedField(field) => write!(f, "Wi-Fi payload field \"{}\" has no name.", field),
+            ParseError::DanglingEscape => write!(f, "Wi-Fi payload ends with an unfinished backslash escape."),
+            ParseError::UnknownAuthType(t) => write!(f, "Unknown authentication type \"{}\".", t),
+            ParseError::UnknownEapMethod(e) => write!(f, "Unknown EAP method \"{}\".", e),
+            ParseError::UnknownPhase2(p) => write!(f, "Unknown phase 2 method \"{}\".", p),
+            ParseError::InvalidHidden(h) => write!(f, "H: must be true or false, not \"{}\".", h),
+            ParseError::InvalidTransitionDisable(r) => write!(f, "R: must be a hex bitmap such as 1, not \"{}\".", r),
+            ParseError::Invalid(e) => e.fmt(f),
+        }
+    }
+}
+impl core::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ParseError::Invalid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+impl From<ValidationError> for ParseError {
+    fn from(e: ValidationError) -> Self {
+        ParseError::Invalid(e)
+    }
+}
+
+/// Reasons a Wi-Fi configuration cannot be encoded as a QR code.
+#[cfg(feature = "std")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum EncodeError {
+    /// The payload does not fit into the largest QR code at the chosen error correction level.
+    DataTooLong,
+}
+#[cfg(feature = "std")]
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            EncodeError::DataTooLong => write!(f, "Wi-Fi payload is too long for a QR code at this error correction level."),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl core::error::Error for EncodeError {}
@@ -0,0 +1,97 @@
+use qrfi::{mecardify, AuthType, CompatProfile, Wifi};
+
+use crate::audit::hex;
+
+/// Describes each field of the payload `wifi.to_mecard()` produces, one line per field, with the password masked.
+pub fn lines(wifi: &Wifi, hex_ssid: bool, sae_as_wpa: bool, profile: CompatProfile) -> Vec<String> {
+    let mut lines = vec!["WIFI: marks the payload as a Wi-Fi network (ZXing's MECARD-like syntax); fields end with ';'.".to_string()];
+
+    let ssid = wifi.ssid();
+    let ssid_line = match ssid.as_str() {
+        _ if hex_ssid => format!("S:{} is the SSID in hex because --hex-ssid is set.", hex(ssid.as_bytes())),
+        None => format!("S:{} is the SSID in hex because it is not UTF-8.", hex(ssid.as_bytes())),
+        Some(raw) => format!("S:{} is the SSID {:?}{}.", ssid.escape(), raw, escaping(raw, &ssid.escape())),
+    };
+
+    let password = wifi.password();
+    let (auth, meaning) = match password.auth_type() {
+        AuthType::Wpa => ("WPA", "WPA, WPA2, or WPA3 Personal; the device picks the version the network offers"),
+        AuthType::Sae if sae_as_wpa => ("WPA", "WPA3 (SAE), labeled WPA because --sae-as-wpa is set"),
+        AuthType::Sae => ("SAE", "WPA3 Personal only (SAE)"),
+        AuthType::Wep => ("WEP", "WEP"),
+        AuthType::Nopass => ("nopass", "an open network without a password"),
+        AuthType::Owe => ("OWE", "Wi-Fi Enhanced Open, which needs no password but encrypts the traffic"),
+        AuthType::Wpa2Eap => ("WPA2-EAP", "WPA2-Enterprise; the device signs in with its own account over 802.1X"),
+    };
+    let auth_line = format!("T:{} means {}.", auth, meaning);
+    if profile == CompatProfile::Ios {
+        lines.extend([auth_line, ssid_line]);
+    } else {
+        lines.extend([ssid_line, auth_line]);
+    }
+
+    let omits_empty = matches!(profile, CompatProfile::Ios | CompatProfile::Loose);
+    lines.push(match password.expose_secret() {
+        None if omits_empty => format!("P: is left out because the password is empty (--compat {}).", name(profile)),
+        None if password.auth_type() == AuthType::Wpa2Eap => "P: is empty because the EAP method needs no password.".to_string(),
+        None => "P: is empty because the network is open.".to_string(),
+        Some(raw) => {
+            let escaped = password.escape();
+            let kind = match password.auth_type() {
+                AuthType::Wpa if raw.len() == 64 => "64-digit hex key".to_string(),
+                AuthType::Wep if [10, 26, 32, 58].contains(&raw.len()) => format!("{}-digit hex key", raw.len()),
+                AuthType::Wpa2Eap => format!("{}-character account password", raw.len()),
+                _ => format!("{}-character passphrase", raw.len()),
+            };
+            format!("P:******** is the {} (masked here){}.", kind, escaping(raw, &escaped))
+        }
+    });
+
+    if let Some(enterprise) = wifi.enterprise() {
+        lines.push(format!("E:{} is the EAP method.", enterprise.method()));
+        if let Some(phase2) = enterprise.phase2() {
+            lines.push(format!("PH2:{} is the inner authentication inside the tunnel.", phase2));
+        }
+        if let Some(identity) = enterprise.identity() {
+            lines.push(format!("I:{} is the identity {:?}.", mecardify(identity), identity));
+        }
+        if let Some(identity) = enterprise.anonymous_identity() {
+            lines.push(format!("A:{} is the anonymous identity {:?}, sent before the tunnel is set up.", mecardify(identity), identity));
+        }
+    }
+
+    if wifi.transition_disable() && matches!(password.auth_type(), AuthType::Wpa | AuthType::Sae) {
+        lines.push("R:1 tells the device to stop falling back to WPA2 once it has joined with WPA3.".to_string());
+    }
+
+    lines.push(if wifi.hidden() {
+        "H:true tells the device to probe for the network because it does not broadcast its SSID.".to_string()
+    } else if profile == CompatProfile::Zxing {
+        "H:false says the network broadcasts its SSID; qrfi always writes H: so every reader sees the same fields.".to_string()
+    } else {
+        format!("H: is left out because the network broadcasts its SSID, which readers assume (--compat {}).", name(profile))
+    });
+    lines.push(if profile == CompatProfile::Loose { "The final ; ends the payload." } else { ";; ends the payload." }.to_string());
+    lines
+}
+
+/// The profile's name on the command line.
+fn name(profile: CompatProfile) -> &'static str {
+    match profile {
+        CompatProfile::Zxing => "zxing",
+        CompatProfile::Ios => "ios",
+        CompatProfile::Android => "android",
+        CompatProfile::Loose => "loose",
+    }
+}
+
+/// Explains how `raw` became `escaped` in the payload.
+fn escaping(raw: &str, escaped: &str) -> String {
+    if escaped.len() == raw.len() + 2 && escaped.starts_with('"') {
+        ", quoted because it consists only of hex digits and would otherwise be read as hex".to_string()
+    } else if escaped.len() > raw.len() {
+        format!(", with {} backslash escape(s) for the reserved characters (\\ ; , :)", escaped.len() - raw.len())
+    } else {
+        ", written as is".to_string()
+    }
+}
@@ -0,0 +1,209 @@
+use clap::ValueEnum;
+use qrfi::AuthType;
+
+/// Formats that configure a device for the network without scanning the code.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum Export {
+    /// NetworkManager shell script (Linux).
+    Nmcli,
+    /// netsh batch file and WLAN profile (Windows).
+    Netsh,
+    /// networksetup shell script (macOS).
+    Networksetup,
+    /// `wifi:` block for ESPHome device configs.
+    Esphome,
+    /// Backlog console command for Tasmota devices.
+    Tasmota,
+}
+
+/// The network fields every export needs.
+pub struct Network<'a> {
+    pub ssid: &'a str,
+    pub auth_type: AuthType,
+    /// `None` for open networks.
+    pub password: Option<&'a str>,
+    pub hidden: bool,
+}
+
+/// Quotes a value for POSIX shells.
+fn sh(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Escapes a value for XML text content.
+fn xml(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Escapes a value inside a double-quoted cmd.exe argument, which [`Export::validate`] keeps free of `"` and
+/// line breaks.
+fn cmd(s: &str) -> String {
+    s.replace('%', "%%")
+}
+
+/// Quotes a value as a YAML double-quoted scalar.
+fn yaml(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', r"\\").replace('"', r#"\""#))
+}
+
+/// Whether the password is a raw key rather than a passphrase.
+fn is_hex_key(network: &Network) -> bool {
+    let p = network.password.unwrap_or_default();
+    let hex_len = match network.auth_type {
+        AuthType::Wep => [10, 26, 32, 58].contains(&p.len()),
+        AuthType::Wpa => p.len() == 64,
+        _ => false,
+    };
+    hex_len && p.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl Export {
+    /// The export's name on the command line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Export::Nmcli => "nmcli",
+            Export::Netsh => "netsh",
+            Export::Networksetup => "networksetup",
+            Export::Esphome => "esphome",
+            Export::Tasmota => "tasmota",
+        }
+    }
+
+    /// Checks that the export can express the network.
+    pub fn validate(self, network: &Network) -> Result<(), String> {
+        match self {
+            _ if network.auth_type == AuthType::Wpa2Eap => {
+                Err("--export does not support WPA2-EAP networks.".to_string())
+            }
+            Export::Networksetup if network.auth_type == AuthType::Sae => {
+                Err("--export networksetup does not support SAE networks.".to_string())
+            }
+            Export::Networksetup | Export::Esphome | Export::Tasmota if network.auth_type == AuthType::Owe => {
+                Err(format!("--export {} does not support OWE networks.", self.as_str()))
+            }
+            Export::Esphome if network.auth_type == AuthType::Wep => {
+                Err("--export esphome does not support WEP networks.".to_string())
+            }
+            // A double quote would end the quoted profile name in cmd.exe and a line break the command, and
+            // neither can be escaped there.
+            Export::Netsh if network.ssid.contains(['"', '\r', '\n']) => {
+                Err("--export netsh cannot express an SSID containing '\"' or a line break.".to_string())
+            }
+            // Backlog splits commands at semicolons and offers no way to escape them.
+            Export::Tasmota if network.ssid.contains(';') || network.password.unwrap_or_default().contains(';') => {
+                Err("--export tasmota cannot express an SSID or password containing ';'.".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Renders the export as a list of `(file name, contents)`.
+    pub fn files(self, network: &Network) -> Vec<(&'static str, String)> {
+        match self {
+            Export::Nmcli => vec![("wifi-nmcli.sh", nmcli(network))],
+            Export::Netsh => vec![
+                ("wifi-netsh.xml", wlan_profile(network)),
+                ("wifi-netsh.cmd", format!(
+                    "@echo off\r\nnetsh wlan add profile filename=\"%~dp0wifi-netsh.xml\" user=all\r\nnetsh wlan connect name=\"{}\"\r\n",
+                    cmd(network.ssid)
+                )),
+            ],
+            Export::Networksetup => vec![("wifi-networksetup.sh", networksetup(network))],
+            Export::Esphome => vec![("wifi-esphome.yaml", esphome(network))],
+            Export::Tasmota => vec![("wifi-tasmota.txt", tasmota(network))],
+        }
+    }
+}
+
+fn nmcli(network: &Network) -> String {
+    let security = match (network.auth_type, network.password) {
+        (AuthType::Wpa, Some(p)) => format!(" wifi-sec.key-mgmt wpa-psk wifi-sec.psk {}", sh(p)),
+        (AuthType::Sae, Some(p)) => format!(" wifi-sec.key-mgmt sae wifi-sec.psk {}", sh(p)),
+        (AuthType::Owe, _) => " wifi-sec.key-mgmt owe".to_string(),
+        // Key type 1 covers both hex keys and 5 or 13 character ASCII keys; 2 would hash the text as a passphrase.
+        (AuthType::Wep, Some(p)) => format!(" wifi-sec.key-mgmt none wifi-sec.wep-key-type 1 wifi-sec.wep-key0 {}", sh(p)),
+        _ => String::new(),
+    };
+    format!(
+        "#!/bin/sh\nset -e\nnmcli connection add type wifi con-name {0} ssid {0} 802-11-wireless.hidden {1}{2}\nnmcli connection up {0}\n",
+        sh(network.ssid), if network.hidden { "yes" } else { "no" }, security
+    )
+}
+
+fn networksetup(network: &Network) -> String {
+    let (security, password) = match (network.auth_type, network.password) {
+        (AuthType::Wpa, Some(p)) => ("WPA2", format!(" {}", sh(p))),
+        (AuthType::Wep, Some(p)) => ("WEP", format!(" {}", sh(p))),
+        _ => ("OPEN", String::new()),
+    };
+    format!(
+        concat!(
+            "#!/bin/sh\n",
+            "set -e\n",
+            "IFACE=$(networksetup -listallhardwareports | awk '/Wi-Fi|AirPort/ {{ getline; print $2; exit }}')\n",
+            "networksetup -addpreferredwirelessnetworkatindex \"$IFACE\" {0} 0 {1}{2}\n",
+            "networksetup -setairportnetwork \"$IFACE\" {0}{2}\n",
+        ),
+        sh(network.ssid), security, password
+    )
+}
+
+fn wlan_profile(network: &Network) -> String {
+    let security = match (network.auth_type, network.password) {
+        (AuthType::Wpa, Some(p)) => format!(
+            concat!(
+                "<authEncryption><authentication>WPA2PSK</authentication><encryption>AES</encryption><useOneX>false</useOneX></authEncryption>",
+                "<sharedKey><keyType>{}</keyType><protected>false</protected><keyMaterial>{}</keyMaterial></sharedKey>",
+            ),
+            if is_hex_key(network) { "networkKey" } else { "passPhrase" }, xml(p)
+        ),
+        (AuthType::Sae, Some(p)) => format!(
+            concat!(
+                "<authEncryption><authentication>WPA3SAE</authentication><encryption>AES</encryption><useOneX>false</useOneX></authEncryption>",
+                "<sharedKey><keyType>passPhrase</keyType><protected>false</protected><keyMaterial>{}</keyMaterial></sharedKey>",
+            ),
+            xml(p)
+        ),
+        (AuthType::Wep, Some(p)) => format!(
+            concat!(
+                "<authEncryption><authentication>open</authentication><encryption>WEP</encryption><useOneX>false</useOneX></authEncryption>",
+                "<sharedKey><keyType>networkKey</keyType><protected>false</protected><keyMaterial>{}</keyMaterial></sharedKey>",
+            ),
+            xml(p)
+        ),
+        (AuthType::Owe, _) => {
+            "<authEncryption><authentication>OWE</authentication><encryption>AES</encryption><useOneX>false</useOneX></authEncryption>".to_string()
+        }
+        _ => "<authEncryption><authentication>open</authentication><encryption>none</encryption><useOneX>false</useOneX></authEncryption>".to_string(),
+    };
+    format!(
+        concat!(
+            "<?xml version=\"1.0\"?>\n",
+            "<WLANProfile xmlns=\"http://www.microsoft.com/networking/WLAN/profile/v1\">\n",
+            "  <name>{0}</name>\n",
+            "  <SSIDConfig><SSID><name>{0}</name></SSID><nonBroadcast>{1}</nonBroadcast></SSIDConfig>\n",
+            "  <connectionType>ESS</connectionType>\n",
+            "  <connectionMode>auto</connectionMode>\n",
+            "  <MSM><security>{2}</security></MSM>\n",
+            "</WLANProfile>\n",
+        ),
+        xml(network.ssid), network.hidden, security
+    )
+}
+
+fn esphome(network: &Network) -> String {
+    let mut yaml_config = format!("wifi:\n  ssid: {}\n", yaml(network.ssid));
+    if let Some(p) = network.password {
+        yaml_config.push_str(&format!("  password: {}\n", yaml(p)));
+    }
+    if network.hidden {
+        // ESPHome only finds hidden networks when it connects without scanning first.
+        yaml_config.push_str("  fast_connect: true\n");
+    }
+    yaml_config
+}
+
+fn tasmota(network: &Network) -> String {
+    // A lone double quote clears the stored password.
+    format!("Backlog SSId1 {}; Password1 {}\n", network.ssid, network.password.unwrap_or("\""))
+}
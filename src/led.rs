@@ -0,0 +1,112 @@
+use crate::QrMatrix;
+
+/// A panel of LEDs that are either on or off, such as a HUB75 matrix or a chain of MAX7219 modules, for showing
+/// a code on a sign.
+///
+/// Implement it over whatever driver or GPIO/SPI bindings the hardware needs, then call [`LedMatrix::draw`].
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{ErrorCorrection, LedMatrix, WifiBuilder};
+///
+/// /// A 64x64 panel in memory.
+/// struct Panel([[bool; 64]; 64]);
+/// impl LedMatrix for Panel {
+///     type Error = core::convert::Infallible;
+///     fn size(&self) -> (usize, usize) {
+///         (64, 64)
+///     }
+///     fn set(&mut self, x: usize, y: usize, on: bool) -> Result<(), Self::Error> {
+///         self.0[y][x] = on;
+///         Ok(())
+///     }
+/// }
+///
+/// let qr = WifiBuilder::new("hackspace").build().unwrap().to_qr(ErrorCorrection::L).unwrap();
+/// let mut panel = Panel([[false; 64]; 64]);
+/// panel.draw(&qr, 4).unwrap();
+/// // 29 modules and the quiet zone at one LED each, centered: the quiet zone starts 13 LEDs in and is lit.
+/// assert!(!panel.0[12][12] && panel.0[13][13]);
+/// ```
+pub trait LedMatrix {
+    /// The error of the underlying driver.
+    type Error;
+
+    /// Columns and rows of LEDs.
+    fn size(&self) -> (usize, usize);
+
+    /// Turns the LED in column `x` of row `y` on or off; both are within [`LedMatrix::size`].
+    fn set(&mut self, x: usize, y: usize, on: bool) -> Result<(), Self::Error>;
+
+    /// Shows what was set. The default does nothing, for panels that update immediately.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Draws `code` with a quiet zone of `margin` modules at the largest whole number of LEDs per module that fits,
+    /// centered, then flushes.
+    ///
+    /// Light modules and the quiet zone are lit and dark modules are off, because many scanners cannot read
+    /// inverted codes. LEDs around the quiet zone are off.
+    ///
+    /// # Errors
+    ///
+    /// [`LedError::TooSmall`] if the panel has fewer LEDs per side than the code has modules, or whatever the driver
+    /// returns.
+    fn draw(&mut self, code: &QrMatrix, margin: usize) -> Result<(), LedError<Self::Error>> {
+        let (columns, rows) = self.size();
+        let modules = margin.saturating_mul(2).saturating_add(code.width());
+        let scale = columns.min(rows) / modules;
+        if scale == 0 {
+            return Err(LedError::TooSmall { modules, columns, rows });
+        }
+        let left = (columns - modules * scale) / 2;
+        let top = (rows - modules * scale) / 2;
+        for y in 0..rows {
+            for x in 0..columns {
+                let on = match (x.checked_sub(left), y.checked_sub(top)) {
+                    (Some(dx), Some(dy)) if dx < modules * scale && dy < modules * scale => {
+                        // Modules outside the code are light, which covers the quiet zone.
+                        match ((dx / scale).checked_sub(margin), (dy / scale).checked_sub(margin)) {
+                            (Some(mx), Some(my)) => !code.is_dark(mx, my),
+                            _ => true,
+                        }
+                    }
+                    _ => false,
+                };
+                self.set(x, y, on).map_err(LedError::Panel)?;
+            }
+        }
+        self.flush().map_err(LedError::Panel)
+    }
+}
+
+/// Reasons [`LedMatrix::draw`] fails.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LedError<E> {
+    /// The code and its quiet zone need more LEDs per side than the panel has.
+    TooSmall { modules: usize, columns: usize, rows: usize },
+    /// The driver failed.
+    Panel(E),
+}
+impl<E: core::fmt::Display> core::fmt::Display for LedError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            LedError::TooSmall { modules, columns, rows } => write!(
+                f,
+                "Code needs {} LEDs per side but the panel is {}x{}; lower the margin or the error correction level.",
+                modules, columns, rows
+            ),
+            LedError::Panel(e) => write!(f, "LED panel error: {}", e),
+        }
+    }
+}
+impl<E: core::error::Error + 'static> core::error::Error for LedError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            LedError::Panel(e) => Some(e),
+            LedError::TooSmall { .. } => None,
+        }
+    }
+}
@@ -1,8 +1,70 @@
-use clap::ValueEnum;
+//! Wi-Fi network configuration and its MECARD-like QR code payload.
+//!
+//! # Stability
+//!
+//! `Wifi`, `WifiBuilder`, `Ssid`, `Password`, `AuthType`, `ValidationPolicy`, `CompatProfile`, `Enterprise`,
+//! `mecard!`, `mecardify`, and `mecard_unescape` follow semver.
+//! New [`Lint`]s may be added in minor releases, but existing codes do not change.
+//! APIs that are still being designed are only compiled with the `unstable` feature and may change in any release.
+//!
+//! # `no_std`
+//!
+//! Without the default `std` feature the crate is `#![no_std]` and only needs `alloc`. The MECARD types work as
+//! usual; `write_svg`, `Wifi::to_qr`, and the conversion to `qrcode::EcLevel` need `std`.
+//!
+//! # Panics
+//!
+//! No function in the library panics, whatever its input: invalid values are reported as errors, so the crate is
+//! safe to call from servers and WASM modules. Clippy's panic, unwrap, expect, and indexing lints enforce this.
+//! Running out of memory is the exception; it aborts as it does for any Rust collection.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::unreachable,
+    clippy::todo,
+    clippy::unimplemented
+)]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[cfg(feature = "unstable")]
+mod dpp;
+mod enterprise;
+mod error;
+#[cfg(feature = "led")]
+mod led;
+mod lint;
+mod macros;
+mod render;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "unstable")]
+pub use dpp::{Channel, Dpp, DppError};
+pub use enterprise::{EapMethod, Enterprise, Phase2};
+pub use error::{ParseError, ValidationError};
+#[cfg(feature = "std")]
+pub use error::EncodeError;
+#[cfg(feature = "led")]
+pub use led::{LedError, LedMatrix};
+pub use lint::Lint;
+#[doc(hidden)]
+pub use macros::__private;
+pub use render::{Colors, ErrorCorrection, RenderOptions, Rgb};
+#[cfg(feature = "std")]
+pub use render::{write_svg, AsciiRenderer, QrMatrix, Renderer, SvgRenderer};
 
 /// Represents a Wi-Fi SSID.
 ///
-/// Validation ensures that the length is between 1 and 32 bytes.
+/// Validation ensures that the length is between 1 and 32 bytes. 802.11 allows any bytes in an SSID, so
+/// [`Ssid::from_bytes`] accepts SSIDs that are not UTF-8; those are written to the payload in hex.
 ///
 /// # Example
 ///
@@ -14,30 +76,91 @@ use clap::ValueEnum;
 ///
 /// let empty_ssid = Ssid::new("".to_string());
 /// assert!(empty_ssid.is_err());
+///
+/// let raw_ssid = Ssid::from_bytes(b"caf\xe9").unwrap();
+/// assert_eq!(raw_ssid.as_str(), None);
+/// assert_eq!(raw_ssid.escape(), "636166e9");
 /// ```
-pub struct Ssid(String);
+pub struct Ssid(Vec<u8>);
 impl Ssid {
     /// Constructor that validates the SSID.
-    pub fn new(s: String) -> Result<Self, String> {
-        let ssid = Self(s);
-        ssid.validate()?;
-        Ok(ssid)
-    }
-    /// Internal validation logic.
-    fn validate(&self) -> Result<(), String> {
-        match self.0.len() {
-            0 => Err("SSID cannot be empty.".to_string()),
+    pub fn new(s: String) -> Result<Self, ValidationError> {
+        Self::validate(s.as_bytes(), ValidationPolicy::Strict)?;
+        Ok(Self(s.into_bytes()))
+    }
+    /// Constructor for SSIDs given as raw bytes, which need not be UTF-8.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ValidationError> {
+        Self::from_bytes_with_policy(bytes, ValidationPolicy::Strict)
+    }
+    /// Like [`Ssid::from_bytes`], but checks only what `policy` asks for.
+    pub fn from_bytes_with_policy(bytes: &[u8], policy: ValidationPolicy) -> Result<Self, ValidationError> {
+        Self::validate(bytes, policy)?;
+        Ok(Self(bytes.to_vec()))
+    }
+    /// Internal validation logic. Lenient validation keeps the limits of 802.11 itself.
+    fn validate(bytes: &[u8], policy: ValidationPolicy) -> Result<(), ValidationError> {
+        if policy == ValidationPolicy::Off {
+            return Ok(());
+        }
+        match bytes.len() {
+            0 => Err(ValidationError::EmptySsid),
             1..=32 => Ok(()),
-            n => Err(format!(
-                "SSID is too long ({} bytes). It must be between 1 and 32 bytes.", n
-            )),
+            bytes => Err(ValidationError::SsidTooLong { bytes }),
+        }
+    }
+    /// The SSID as it appears in the payload: escaped if it is UTF-8, lowercase hex otherwise.
+    ///
+    /// UTF-8 SSIDs made only of hex digits are wrapped in double quotes, as ZXing specifies, so readers do not
+    /// decode them as hex.
+    ///
+    /// The result borrows from the SSID unless something had to change.
+    pub fn escape(&self) -> Cow<'_, str> {
+        match self.as_str() {
+            Some(s) if !is_hex_lookalike(s) => mecardify(s),
+            _ => {
+                Cow::Owned(write_to_string(|out| self.write_escaped(out)))
+            }
+        }
+    }
+    fn write_escaped<W: core::fmt::Write + ?Sized>(&self, out: &mut W) -> core::fmt::Result {
+        match core::str::from_utf8(&self.0) {
+            Ok(s) => write_quoted(out, s),
+            Err(_) => self.write_hex(out),
+        }
+    }
+    fn write_hex<W: core::fmt::Write + ?Sized>(&self, out: &mut W) -> core::fmt::Result {
+        for byte in &self.0 {
+            write!(out, "{:02x}", byte)?;
         }
+        Ok(())
+    }
+    /// The SSID as given, without escaping, or `None` if it is not UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        core::str::from_utf8(&self.0).ok()
+    }
+    /// The SSID as given, without escaping.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
     }
-    pub fn escape(&self) -> String {
-        mecardify(&self.0)
+}
+impl core::fmt::Debug for Ssid {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.as_str() {
+            Some(s) => f.debug_tuple("Ssid").field(&s).finish(),
+            None => f.debug_tuple("Ssid").field(&self.0).finish(),
+        }
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl Drop for Ssid {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Ssid {}
+
 /// Represents a Wi-Fi password and its authentication method.
 ///
 /// # Example
@@ -56,59 +179,156 @@ pub struct Password {
 }
 impl Password {
     /// Constructor that enforces business rules:
-    /// If AuthType is Nopass, the password value is forced to None.
-    pub fn new(value: Option<String>, auth_type: AuthType) -> Result<Self, String> {
-        let actual_value = if auth_type == AuthType::Nopass {
-            None
-        } else {
-            value
+    /// If AuthType is Nopass, the password value is forced to None, and an empty WPA2-EAP password is None too.
+    pub fn new(value: Option<String>, auth_type: AuthType) -> Result<Self, ValidationError> {
+        Self::new_with_policy(value, auth_type, ValidationPolicy::Strict)
+    }
+
+    /// Like [`Password::new`], but checks only what `policy` asks for.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::{AuthType, Password, ValidationPolicy};
+    ///
+    /// let umlauts = Some("Passwört!".to_string());
+    /// assert!(Password::new(umlauts.clone(), AuthType::Wpa).is_err());
+    /// assert!(Password::new_with_policy(umlauts, AuthType::Wpa, ValidationPolicy::Lenient).is_ok());
+    /// ```
+    pub fn new_with_policy(value: Option<String>, auth_type: AuthType, policy: ValidationPolicy) -> Result<Self, ValidationError> {
+        let actual_value = match auth_type {
+            AuthType::Nopass => None,
+            AuthType::Wpa2Eap => value.filter(|v| !v.is_empty()),
+            _ => value,
         };
 
-        let pass = Self {
+        Self::validate(actual_value.as_deref(), auth_type, policy)?;
+        Ok(Self {
             value: actual_value,
             auth_type,
-        };
-        pass.validate()?;
-        Ok(pass)
+        })
     }
 
-    fn validate(&self) -> Result<(), String> {
-        let p = self.value.as_deref().unwrap_or("");
+    fn validate(value: Option<&str>, auth_type: AuthType, policy: ValidationPolicy) -> Result<(), ValidationError> {
+        if policy == ValidationPolicy::Off {
+            return Ok(());
+        }
+        let p = value.unwrap_or("");
         let len = p.len();
         let is_hex = !p.is_empty() && p.chars().all(|c| c.is_ascii_hexdigit());
         let is_printable_ascii = !p.is_empty() && p.is_ascii() && p.chars().all(|c| (0x20..=0x7E).contains(&(c as u8)));
 
-        match self.auth_type {
-            AuthType::Nopass => {
+        match auth_type {
+            AuthType::Nopass | AuthType::Owe => {
                 if !p.is_empty() {
-                    return Err("Password should not be provided for 'nopass'.".to_string());
+                    return Err(ValidationError::UnexpectedPassword);
                 }
             }
             AuthType::Wpa => {
                 let is_valid_hex = len == 64 && is_hex;
-                let is_valid_ascii = (8..=63).contains(&len) && is_printable_ascii;
+                // Many access points take any UTF-8 passphrase of the right length, though the standard does not.
+                let is_valid_ascii = (8..=63).contains(&len) && (is_printable_ascii || policy == ValidationPolicy::Lenient);
                 if !(is_valid_ascii || is_valid_hex) {
-                    return Err("WPA passphrase must be 8-63 printable ASCII characters, or 64 hex digits.".to_string());
+                    return Err(ValidationError::InvalidWpaPassphrase { bytes: len, policy });
                 }
             }
             AuthType::Wep => {
-                let is_valid_hex = (len == 10 || len == 26) && is_hex;
-                if !([5, 13].contains(&len) || is_valid_hex) {
-                    return Err("WEP password must be 5 or 13 characters, or 10 or 26 hex digits.".to_string());
+                // Some legacy gear also takes 152-bit and 256-bit keys, which no standard defines.
+                let (ascii, hex): (&[usize], &[usize]) = match policy {
+                    ValidationPolicy::Lenient => (&[5, 13, 16, 29], &[10, 26, 32, 58]),
+                    _ => (&[5, 13], &[10, 26]),
+                };
+                let is_valid_hex = hex.contains(&len) && is_hex;
+                if !(ascii.contains(&len) || is_valid_hex) {
+                    return Err(ValidationError::InvalidWepKey { bytes: len, policy });
                 }
             }
+            // SAE has no length limits and no raw key form.
+            AuthType::Sae => {
+                if p.is_empty() {
+                    return Err(ValidationError::EmptySaePassword);
+                }
+            }
+            // The account password is checked by the RADIUS server, not the access point, so anything goes.
+            AuthType::Wpa2Eap => {}
         }
         Ok(())
     }
 
-    pub fn escape(&self) -> String {
-        mecardify(self.value.as_deref().unwrap_or_default())
+    /// The password as it appears in the payload.
+    ///
+    /// Passphrases made only of hex digits are wrapped in double quotes so readers do not take them for raw keys.
+    /// Raw keys, 64 hex digits for WPA or 10, 26, 32, or 58 for WEP, are left bare. The result borrows from the
+    /// password unless something had to change.
+    pub fn escape(&self) -> Cow<'_, str> {
+        let value = self.value.as_deref().unwrap_or_default();
+        if self.is_raw_key() || !is_hex_lookalike(value) {
+            mecardify(value)
+        } else {
+            Cow::Owned(write_to_string(|out| write_quoted(out, value)))
+        }
+    }
+    fn write_escaped<W: core::fmt::Write + ?Sized>(&self, out: &mut W) -> core::fmt::Result {
+        let value = self.value.as_deref().unwrap_or_default();
+        if self.is_raw_key() {
+            write_escaped(out, value)
+        } else {
+            write_quoted(out, value)
+        }
+    }
+    /// Whether the password is a hex key rather than a passphrase, judging by its length; validation already
+    /// ensured the digits.
+    fn is_raw_key(&self) -> bool {
+        let len = self.value.as_ref().map_or(0, String::len);
+        match self.auth_type {
+            AuthType::Wpa => len == 64,
+            AuthType::Wep => [10, 26, 32, 58].contains(&len),
+            AuthType::Nopass | AuthType::Owe | AuthType::Sae | AuthType::Wpa2Eap => false,
+        }
     }
 
     pub fn auth_type(&self) -> AuthType {
         self.auth_type
     }
+
+    /// The password as given, without escaping. `None` for open networks.
+    ///
+    /// The name marks every place that handles the plaintext; `Debug` output never includes it.
+    pub fn expose_secret(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+}
+impl core::fmt::Debug for Password {
+    /// Shows the authentication type and password length, but masks the password itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::{AuthType, Password};
+    ///
+    /// let password = Password::new(Some("PASSWORD".to_string()), AuthType::Wpa).unwrap();
+    /// assert_eq!(
+    ///     format!("{:?}", password),
+    ///     r#"Password { auth_type: Wpa, value: Some("********"), len: 8 }"#
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Password")
+            .field("auth_type", &self.auth_type)
+            .field("value", &self.value.as_ref().map(|_| "********"))
+            .field("len", &self.value.as_ref().map_or(0, |value| value.len()))
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Password {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.value);
+    }
 }
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Password {}
 
 /// Represents a Wi-Fi configuration and handles its conversion to the MECARD-like syntax proposed by ZXing.
 ///
@@ -122,7 +342,11 @@ impl Password {
 /// let wifi = Wifi::new(ssid, password, false);
 ///
 /// assert_eq!(wifi.to_mecard(), "WIFI:S:SSID;T:WPA;P:PASSWORD;H:false;;");
+///
+/// let parsed: Wifi = wifi.to_string().parse().unwrap();
+/// assert_eq!(parsed.to_string(), wifi.to_string());
 /// ```
+#[derive(Debug)]
 pub struct Wifi {
     /// The SSID (Service Set Identifier) of the Wi-Fi network.
     ssid: Ssid,
@@ -130,45 +354,820 @@ pub struct Wifi {
     password: Password,
     /// Whether the Wi-Fi network's SSID is hidden (not broadcasted).
     hidden: bool,
+    /// Whether the payload carries the SSID in hex even if it is UTF-8.
+    hex_ssid: bool,
+    /// The 802.1X settings of a `WPA2-EAP` network.
+    enterprise: Option<Enterprise>,
+    /// Whether devices must stop falling back to WPA2 once they joined with WPA3.
+    transition_disable: bool,
+    /// Whether an SAE network is labeled `T:WPA` for readers that do not know `T:SAE`.
+    sae_as_wpa: bool,
+    /// The field order and optional fields of the payload.
+    profile: CompatProfile,
 }
 impl Wifi {
+    /// Longest string [`Wifi::from_mecard`] accepts: the byte capacity of the largest QR code (version 40-L).
+    ///
+    /// Nothing longer can come from a scanned code, so rejecting it up front bounds the work done on untrusted input.
+    pub const MAX_MECARD_LEN: usize = 2953;
+
     /// Since Ssid and Password are already validated, Wifi::new is always safe.
+    ///
+    /// A `WPA2-EAP` network also needs its EAP method, see [`Wifi::with_enterprise`].
     pub fn new(ssid: Ssid, password: Password, hidden: bool) -> Self {
-        Self { ssid, password, hidden }
+        Self {
+            ssid,
+            password,
+            hidden,
+            hex_ssid: false,
+            enterprise: None,
+            transition_disable: false,
+            sae_as_wpa: false,
+            profile: CompatProfile::Zxing,
+        }
+    }
+
+    /// Checks an SSID and password like [`Ssid::from_bytes`] and [`Password::new`] do, but reports every
+    /// problem instead of stopping at the first. An empty list means both are valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::{AuthType, ValidationError, ValidationPolicy, Wifi};
+    ///
+    /// let errors = Wifi::validate_all(b"", Some("short"), AuthType::Wpa);
+    /// assert_eq!(errors, [ValidationError::EmptySsid, ValidationError::InvalidWpaPassphrase { bytes: 5, policy: ValidationPolicy::Strict }]);
+    /// assert!(Wifi::validate_all(b"SSID", None, AuthType::Nopass).is_empty());
+    /// ```
+    pub fn validate_all(ssid: &[u8], password: Option<&str>, auth_type: AuthType) -> Vec<ValidationError> {
+        Self::validate_all_with_policy(ssid, password, auth_type, ValidationPolicy::Strict)
+    }
+
+    /// Like [`Wifi::validate_all`], but checks only what `policy` asks for.
+    pub fn validate_all_with_policy(
+        ssid: &[u8], password: Option<&str>, auth_type: AuthType, policy: ValidationPolicy,
+    ) -> Vec<ValidationError> {
+        let password = if auth_type == AuthType::Nopass { None } else { password };
+        let ssid = Ssid::validate(ssid, policy).err();
+        ssid.into_iter().chain(Password::validate(password, auth_type, policy).err()).collect()
+    }
+
+    /// Writes the SSID as unquoted hex, the ZXing convention for binary SSIDs.
+    ///
+    /// Use this for SSIDs with characters that some readers mangle when unescaping. Readers that do not
+    /// understand hex SSIDs will show the digits instead of the name. [`Wifi::from_mecard`] decodes them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::{Wifi, WifiBuilder};
+    ///
+    /// let wifi = WifiBuilder::new("a;b").build().unwrap().with_hex_ssid(true);
+    /// assert_eq!(wifi.to_mecard(), "WIFI:S:613b62;T:nopass;P:;H:false;;");
+    /// assert_eq!(Wifi::from_mecard(&wifi.to_mecard()).unwrap().ssid().as_str(), Some("a;b"));
+    /// ```
+    pub fn with_hex_ssid(mut self, hex_ssid: bool) -> Self {
+        self.hex_ssid = hex_ssid;
+        self
+    }
+
+    /// Sets the WPA3 transition disable indicator `R:1`, which tells devices to use only WPA3 (SAE) for this
+    /// network from now on, even if the access point still offers WPA2.
+    ///
+    /// Only written for `WPA` and `SAE` networks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::WifiBuilder;
+    ///
+    /// let wifi = WifiBuilder::new("SSID").wpa("PASSWORD").build().unwrap().with_transition_disable(true);
+    /// assert_eq!(wifi.to_mecard(), "WIFI:S:SSID;T:WPA;P:PASSWORD;R:1;H:false;;");
+    /// ```
+    pub fn with_transition_disable(mut self, transition_disable: bool) -> Self {
+        self.transition_disable = transition_disable;
+        self
+    }
+
+    /// Labels an SAE network `T:WPA` instead of `T:SAE`.
+    ///
+    /// Most readers predate `T:SAE` and reject the code, while `T:WPA` lets the device pick WPA3 if the network
+    /// offers it. Passwords outside 8-63 characters still only work with readers that understand SAE.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::WifiBuilder;
+    ///
+    /// let wifi = WifiBuilder::new("SSID").sae("PASSWORD").build().unwrap();
+    /// assert_eq!(wifi.to_mecard(), "WIFI:S:SSID;T:SAE;P:PASSWORD;H:false;;");
+    /// assert_eq!(wifi.with_sae_as_wpa(true).to_mecard(), "WIFI:S:SSID;T:WPA;P:PASSWORD;H:false;;");
+    /// ```
+    pub fn with_sae_as_wpa(mut self, sae_as_wpa: bool) -> Self {
+        self.sae_as_wpa = sae_as_wpa;
+        self
+    }
+
+    /// Lays out the payload for readers that disagree on field order, optional fields, and the end of the payload.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::{CompatProfile, WifiBuilder};
+    ///
+    /// let wifi = WifiBuilder::new("SSID").wpa("PASSWORD").build().unwrap();
+    /// assert_eq!(wifi.to_mecard(), "WIFI:S:SSID;T:WPA;P:PASSWORD;H:false;;");
+    /// assert_eq!(wifi.with_compat_profile(CompatProfile::Ios).to_mecard(), "WIFI:T:WPA;S:SSID;P:PASSWORD;;");
+    /// ```
+    pub fn with_compat_profile(mut self, profile: CompatProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Adds the EAP method and identities of a `WPA2-EAP` network, written after the password.
+    ///
+    /// # Errors
+    ///
+    /// [`ValidationError::UnexpectedEapSettings`] if the password is not for `WPA2-EAP`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::{AuthType, EapMethod, Enterprise, Password, Ssid, Wifi};
+    ///
+    /// let ssid = Ssid::new("campus".to_string()).unwrap();
+    /// let password = Password::new(None, AuthType::Wpa2Eap).unwrap();
+    /// let wifi = Wifi::new(ssid, password, false).with_enterprise(Enterprise::new(EapMethod::Tls)).unwrap();
+    /// assert_eq!(wifi.to_mecard(), "WIFI:S:campus;T:WPA2-EAP;P:;E:TLS;H:false;;");
+    /// ```
+    pub fn with_enterprise(mut self, enterprise: Enterprise) -> Result<Self, ValidationError> {
+        if self.password.auth_type != AuthType::Wpa2Eap {
+            return Err(ValidationError::UnexpectedEapSettings);
+        }
+        self.enterprise = Some(enterprise);
+        Ok(self)
     }
 
     pub fn to_mecard(&self) -> String {
-        format!(
-            "WIFI:S:{};T:{};P:{};H:{};;",
-            self.ssid.escape(),
-            self.password.auth_type(),
-            self.password.escape(),
-            if self.hidden { "true" } else { "false" }
-        )
+        write_to_string(|out| self.to_mecard_into(out))
+    }
+
+    /// Writes the `WIFI:` string to `out` without allocating, for reusable buffers and streaming writers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::WifiBuilder;
+    ///
+    /// let mut buffer = String::new();
+    /// for ssid in ["lobby", "guest"] {
+    ///     buffer.clear();
+    ///     WifiBuilder::new(ssid).build().unwrap().to_mecard_into(&mut buffer).unwrap();
+    ///     assert_eq!(buffer, format!("WIFI:S:{};T:nopass;P:;H:false;;", ssid));
+    /// }
+    /// ```
+    pub fn to_mecard_into<W: core::fmt::Write + ?Sized>(&self, out: &mut W) -> core::fmt::Result {
+        let auth = match self.password.auth_type {
+            AuthType::Wep => "WEP",
+            AuthType::Wpa => "WPA",
+            AuthType::Nopass => "nopass",
+            AuthType::Owe => "OWE",
+            AuthType::Sae if self.sae_as_wpa => "WPA",
+            AuthType::Sae => "SAE",
+            AuthType::Wpa2Eap => "WPA2-EAP",
+        };
+        let profile = self.profile;
+        if profile == CompatProfile::Ios {
+            write!(out, "WIFI:T:{};S:", auth)?;
+        } else {
+            out.write_str("WIFI:S:")?;
+        }
+        if self.hex_ssid {
+            self.ssid.write_hex(out)?;
+        } else {
+            self.ssid.write_escaped(out)?;
+        }
+        if profile != CompatProfile::Ios {
+            write!(out, ";T:{}", auth)?;
+        }
+        if self.password.value.is_some() || matches!(profile, CompatProfile::Zxing | CompatProfile::Android) {
+            out.write_str(";P:")?;
+            self.password.write_escaped(out)?;
+        }
+        if let Some(enterprise) = &self.enterprise {
+            enterprise.write_fields(out)?;
+        }
+        if self.transition_disable && matches!(self.password.auth_type, AuthType::Wpa | AuthType::Sae) {
+            out.write_str(";R:1")?;
+        }
+        if self.hidden {
+            out.write_str(";H:true")?;
+        } else if profile == CompatProfile::Zxing {
+            out.write_str(";H:false")?;
+        }
+        out.write_str(if profile == CompatProfile::Loose { ";" } else { ";;" })
+    }
+
+    /// The `WIFI:` URI of the WPA3 specification, for scanners that implement the Wi-Fi Alliance's format rather
+    /// than ZXing's.
+    ///
+    /// The fields come in the order `T`, `R`, `S`, `H`, `P`, and values are percent-encoded instead of
+    /// backslash-escaped: `;`, `%`, and bytes outside printable ASCII become `%XX`, so hex lookalikes need no
+    /// quotes and binary SSIDs no hex. Open networks have no `T:` and broadcast networks no `H:`. The URI has no
+    /// fields for 802.1X, so the settings of a `WPA2-EAP` network are left out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::WifiBuilder;
+    ///
+    /// let wifi = WifiBuilder::new("Guest;Lobby").sae("50% off").build().unwrap().with_transition_disable(true);
+    /// assert_eq!(wifi.to_uri(), "WIFI:T:SAE;R:1;S:Guest%3BLobby;P:50%25 off;;");
+    /// assert_eq!(WifiBuilder::new("cafe").hidden(true).build().unwrap().to_uri(), "WIFI:S:cafe;H:true;;");
+    /// ```
+    pub fn to_uri(&self) -> String {
+        write_to_string(|out| self.to_uri_into(out))
+    }
+
+    /// Writes the URI of [`Wifi::to_uri`] to `out` without allocating.
+    pub fn to_uri_into<W: core::fmt::Write + ?Sized>(&self, out: &mut W) -> core::fmt::Result {
+        out.write_str("WIFI:")?;
+        match self.password.auth_type {
+            AuthType::Nopass => {}
+            AuthType::Wep => out.write_str("T:WEP;")?,
+            AuthType::Wpa => out.write_str("T:WPA;")?,
+            AuthType::Owe => out.write_str("T:OWE;")?,
+            AuthType::Sae if self.sae_as_wpa => out.write_str("T:WPA;")?,
+            AuthType::Sae => out.write_str("T:SAE;")?,
+            AuthType::Wpa2Eap => out.write_str("T:WPA2-EAP;")?,
+        }
+        if self.transition_disable && matches!(self.password.auth_type, AuthType::Wpa | AuthType::Sae) {
+            out.write_str("R:1;")?;
+        }
+        out.write_str("S:")?;
+        write_percent_encoded(out, &self.ssid.0)?;
+        out.write_str(";")?;
+        if self.hidden {
+            out.write_str("H:true;")?;
+        }
+        if let Some(password) = &self.password.value {
+            out.write_str("P:")?;
+            write_percent_encoded(out, password.as_bytes())?;
+            out.write_str(";")?;
+        }
+        out.write_str(";")
+    }
+
+    /// Encodes the `WIFI:` string as a QR code, so applications can draw the modules however they like.
+    #[cfg(feature = "std")]
+    pub fn to_qr(&self, ecl: ErrorCorrection) -> Result<QrMatrix, EncodeError> {
+        QrMatrix::encode(self.to_mecard().as_bytes(), ecl)
+    }
+
+    /// Encodes the `WIFI:` string and yields every module as `(x, y, dark)`, row by row from the top left, so custom
+    /// renderers such as LED matrices and plotters need neither [`QrMatrix`] nor the `qrcode` crate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::{ErrorCorrection, WifiBuilder};
+    ///
+    /// let wifi = WifiBuilder::new("SSID").wpa("PASSWORD").build().unwrap();
+    /// for (x, y, dark) in wifi.modules(ErrorCorrection::M).unwrap() {
+    ///     if dark {
+    ///         // plot(x, y);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn modules(&self, ecl: ErrorCorrection) -> Result<impl Iterator<Item = (usize, usize, bool)>, EncodeError> {
+        let qr = self.to_qr(ecl)?;
+        let width = qr.width();
+        // The modules are empty if the width is 0, so the closure never divides by it.
+        Ok(qr.into_modules().into_iter().enumerate().map(move |(i, dark)| (i % width, i / width, dark)))
+    }
+
+    /// Like [`Wifi::to_mecard`], but the string is wiped from memory when dropped.
+    #[cfg(feature = "zeroize")]
+    pub fn to_mecard_zeroizing(&self) -> zeroize::Zeroizing<String> {
+        zeroize::Zeroizing::new(self.to_mecard())
+    }
+
+    /// Parses a `WIFI:` string such as one produced by [`Wifi::to_mecard`] or another generator.
+    ///
+    /// Fields may appear in any order and unknown fields are ignored. A missing `H:` means a broadcast
+    /// network, `R:` is read as a hex bitmap whose lowest bit is the WPA3 transition disable indicator, and a
+    /// missing `T:` means `nopass` unless a `P:` field is present, in which case WPA is assumed. Empty `H:`, `R:`,
+    /// and `T:` fields count as missing.
+    /// An unquoted SSID made only of an even number of hex digits is decoded as hex, the ZXing convention that
+    /// [`Wifi::with_hex_ssid`] and binary SSIDs follow; double quotes around an SSID or password made only of hex
+    /// digits are removed and keep it as text. The `E:`, `PH2:`, `I:`, and `A:` fields are only read for
+    /// `WPA2-EAP` networks, which must have an `E:`. Strings longer than [`Wifi::MAX_MECARD_LEN`] are rejected
+    /// before parsing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::{AuthType, Wifi};
+    ///
+    /// let wifi = Wifi::from_mecard(r"WIFI:T:WPA;P:pass\;word;S:Caf\:e;;").unwrap();
+    /// assert_eq!(wifi.ssid().as_str(), Some("Caf:e"));
+    /// assert_eq!(wifi.password().expose_secret(), Some("pass;word"));
+    /// assert_eq!(wifi.password().auth_type(), AuthType::Wpa);
+    /// assert!(!wifi.hidden());
+    /// ```
+    pub fn from_mecard(s: &str) -> Result<Self, ParseError> {
+        if s.len() > Self::MAX_MECARD_LEN {
+            return Err(ParseError::TooLong { bytes: s.len() });
+        }
+        let body = s.strip_prefix("WIFI:").ok_or(ParseError::MissingPrefix)?;
+        let (mut ssid, mut auth_type, mut password, mut hidden, mut transition_disable) = (None, None, None, false, false);
+        let (mut method, mut phase2, mut identity, mut anonymous_identity) = (None, None, None, None);
+        for field in split_fields(body) {
+            if field.is_empty() {
+                continue;
+            }
+            let (name, value) = field.split_once(':').ok_or_else(|| ParseError::MalformedField(field.to_string()))?;
+            let value = mecard_unescape(value)?;
+            match name {
+                "S" => ssid = Some(value),
+                // An empty `T:` says as little as a missing one, so the password decides.
+                "T" if value.is_empty() => auth_type = None,
+                "T" => auth_type = Some(value.parse()?),
+                "P" => password = Some(unquote(value)).filter(|p| !p.is_empty()),
+                "H" => hidden = match value.as_str() {
+                    "true" => true,
+                    "false" | "" => false,
+                    _ => return Err(ParseError::InvalidHidden(value)),
+                },
+                "R" if value.is_empty() => transition_disable = false,
+                "R" => match u8::from_str_radix(&value, 16) {
+                    Ok(bitmap) => transition_disable = bitmap & 1 == 1,
+                    Err(_) => return Err(ParseError::InvalidTransitionDisable(value)),
+                },
+                "E" => method = Some(value),
+                "PH2" => phase2 = Some(value).filter(|p| !p.is_empty()),
+                "I" => identity = Some(value),
+                "A" => anonymous_identity = Some(value),
+                _ => {}
+            }
+        }
+        let ssid = ssid.ok_or(ParseError::MissingSsid)?;
+        let (ssid, hex_ssid) = match decode_hex(&ssid) {
+            // Text SSIDs only come out in hex when the generator asked for it, so keep asking on the way back.
+            Some(bytes) => (Ssid::from_bytes(&bytes)?, core::str::from_utf8(&bytes).is_ok()),
+            None => (Ssid::new(unquote(ssid))?, false),
+        };
+        let auth_type = auth_type.unwrap_or(if password.is_some() { AuthType::Wpa } else { AuthType::Nopass });
+        let password = Password::new(password, auth_type)?;
+        let wifi = Self::new(ssid, password, hidden).with_hex_ssid(hex_ssid).with_transition_disable(transition_disable);
+        if auth_type != AuthType::Wpa2Eap {
+            return Ok(wifi);
+        }
+        let mut enterprise = Enterprise::new(method.ok_or(ValidationError::MissingEapMethod)?.parse()?);
+        if let Some(phase2) = phase2 {
+            enterprise = enterprise.with_phase2(phase2.parse()?);
+        }
+        if let Some(identity) = identity {
+            enterprise = enterprise.with_identity(identity);
+        }
+        if let Some(identity) = anonymous_identity {
+            enterprise = enterprise.with_anonymous_identity(identity);
+        }
+        Ok(wifi.with_enterprise(enterprise)?)
+    }
+
+    /// Likely mistakes that still make a valid configuration, see [`Lint`].
+    pub fn lints(&self) -> Vec<Lint> {
+        let mut lints = Vec::new();
+        match self.ssid.as_str() {
+            Some(ssid) => {
+                if ssid.trim() != ssid {
+                    lints.push(Lint::SsidWhitespace);
+                }
+                if ssid.chars().any(char::is_control) {
+                    lints.push(Lint::SsidControlCharacter);
+                }
+            }
+            None => lints.push(Lint::SsidNotUtf8),
+        }
+        let password = self.password.value.as_deref().unwrap_or_default();
+        if password.trim() != password {
+            lints.push(Lint::PasswordWhitespace);
+        }
+        if self.password.auth_type == AuthType::Wpa && !password.is_ascii() {
+            lints.push(Lint::PasswordNotAscii);
+        }
+        if self.password.auth_type == AuthType::Wep {
+            lints.push(Lint::Wep);
+        }
+        lints
+    }
+
+    pub fn ssid(&self) -> &Ssid {
+        &self.ssid
+    }
+
+    pub fn password(&self) -> &Password {
+        &self.password
+    }
+
+    pub fn hidden(&self) -> bool {
+        self.hidden
+    }
+
+    pub fn enterprise(&self) -> Option<&Enterprise> {
+        self.enterprise.as_ref()
     }
+
+    pub fn transition_disable(&self) -> bool {
+        self.transition_disable
+    }
+}
+
+impl core::fmt::Display for Wifi {
+    /// Formats the network as its `WIFI:` string, see [`Wifi::to_mecard`].
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.to_mecard_into(f)
+    }
+}
+impl core::str::FromStr for Wifi {
+    type Err = ParseError;
+
+    /// Parses a `WIFI:` string, see [`Wifi::from_mecard`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Wifi::from_mecard(s)
+    }
+}
+
+/// Builds a [`Wifi`], validating every field in [`WifiBuilder::build`].
+///
+/// The network is open until a password is set with [`WifiBuilder::wpa`], [`WifiBuilder::sae`],
+/// [`WifiBuilder::wep`], or [`WifiBuilder::eap`].
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{ValidationError, ValidationPolicy, WifiBuilder};
+///
+/// let wifi = WifiBuilder::new("SSID").wpa("PASSWORD").hidden(true).build().unwrap();
+/// assert_eq!(wifi.to_mecard(), "WIFI:S:SSID;T:WPA;P:PASSWORD;H:true;;");
+///
+/// let err = WifiBuilder::new("SSID").wpa("short").build().err();
+/// assert_eq!(err, Some(ValidationError::InvalidWpaPassphrase { bytes: 5, policy: ValidationPolicy::Strict }));
+/// ```
+#[derive(Clone)]
+pub struct WifiBuilder {
+    ssid: String,
+    auth_type: AuthType,
+    password: Option<String>,
+    hidden: bool,
+    transition_disable: bool,
+    enterprise: Option<Enterprise>,
+    policy: ValidationPolicy,
+}
+impl core::fmt::Debug for WifiBuilder {
+    /// Masks the password like [`Password`]'s `Debug` does.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("WifiBuilder")
+            .field("ssid", &self.ssid)
+            .field("auth_type", &self.auth_type)
+            .field("password", &self.password.as_ref().map(|_| "********"))
+            .field("hidden", &self.hidden)
+            .field("transition_disable", &self.transition_disable)
+            .field("enterprise", &self.enterprise)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+impl WifiBuilder {
+    pub fn new(ssid: impl Into<String>) -> Self {
+        Self {
+            ssid: ssid.into(),
+            auth_type: AuthType::Nopass,
+            password: None,
+            hidden: false,
+            transition_disable: false,
+            enterprise: None,
+            policy: ValidationPolicy::Strict,
+        }
+    }
+
+    /// Secures the network with a WPA/WPA2/WPA3 passphrase or 64-digit hex key.
+    pub fn wpa(mut self, password: impl Into<String>) -> Self {
+        self.auth_type = AuthType::Wpa;
+        self.password = Some(password.into());
+        self.enterprise = None;
+        self
+    }
+
+    /// Secures the network with a WPA3 (SAE) password of any length.
+    pub fn sae(mut self, password: impl Into<String>) -> Self {
+        self.auth_type = AuthType::Sae;
+        self.password = Some(password.into());
+        self.enterprise = None;
+        self
+    }
+
+    /// Secures the network with a WEP key.
+    pub fn wep(mut self, key: impl Into<String>) -> Self {
+        self.auth_type = AuthType::Wep;
+        self.password = Some(key.into());
+        self.enterprise = None;
+        self
+    }
+
+    /// Makes the network open, dropping any password set before.
+    pub fn nopass(mut self) -> Self {
+        self.auth_type = AuthType::Nopass;
+        self.password = None;
+        self.enterprise = None;
+        self
+    }
+
+    /// Makes the network Enhanced Open (OWE), dropping any password set before.
+    pub fn owe(mut self) -> Self {
+        self.auth_type = AuthType::Owe;
+        self.password = None;
+        self.enterprise = None;
+        self
+    }
+
+    /// Makes the network WPA2-Enterprise with the given EAP settings and account password, if the method uses one.
+    pub fn eap(mut self, enterprise: Enterprise, password: Option<String>) -> Self {
+        self.auth_type = AuthType::Wpa2Eap;
+        self.password = password;
+        self.enterprise = Some(enterprise);
+        self
+    }
+
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// See [`Wifi::with_transition_disable`].
+    pub fn transition_disable(mut self, transition_disable: bool) -> Self {
+        self.transition_disable = transition_disable;
+        self
+    }
+
+    /// Relaxes the checks of [`WifiBuilder::build`], see [`ValidationPolicy`].
+    pub fn policy(mut self, policy: ValidationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Every problem [`WifiBuilder::build`] could report, see [`Wifi::validate_all`].
+    pub fn validate_all(&self) -> Vec<ValidationError> {
+        Wifi::validate_all_with_policy(self.ssid.as_bytes(), self.password.as_deref(), self.auth_type, self.policy)
+    }
+
+    /// Validates the SSID and password and builds the configuration.
+    pub fn build(self) -> Result<Wifi, ValidationError> {
+        let ssid = Ssid::from_bytes_with_policy(self.ssid.as_bytes(), self.policy)?;
+        let password = Password::new_with_policy(self.password, self.auth_type, self.policy)?;
+        let wifi = Wifi::new(ssid, password, self.hidden).with_transition_disable(self.transition_disable);
+        match self.enterprise {
+            Some(enterprise) => wifi.with_enterprise(enterprise),
+            None => Ok(wifi),
+        }
+    }
+}
+
+/// Splits a MECARD body on `;` that are not escaped, keeping escapes intact.
+fn split_fields(body: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in body.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ';' => {
+                fields.push(&body[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    fields.push(&body[start..]);
+    fields
+}
+
+/// `WIFI:` strings covering every branch of [`Wifi::from_mecard`], valid and invalid, for seeding fuzzers and
+/// the test suites of other readers.
+#[cfg(feature = "unstable")]
+pub const PARSER_CORPUS: &[&str] = &[
+    "WIFI:S:guest;T:WPA;P:password;H:false;;",
+    "WIFI:S:guest;T:nopass;P:;H:true;;",
+    r"WIFI:S:a\;b\,c\:d\\e;T:WEP;P:abcde;;",
+    r#"WIFI:S:"cafe";T:WPA;P:"12345678";;"#,
+    "WIFI:S:lobby;T:WEP;P:0123456789;;",
+    "WIFI:P:password;S:guest;X:ignored;;",
+    "WIFI:S:guest;",
+    "WIFI:S:guest;T:wpa2-psk;P:password;H:;;",
+    "WIFI:S:;;",
+    "WIFI:T:WPA;P:password;;",
+    "WIFI:S:guest;oops;;",
+    "WIFI:S:guest\\",
+    "WIFI:S:guest;T:WPA4;;",
+    "WIFI:S:guest;H:maybe;;",
+    "WIFI:S:guest;T:WPA;P:short;;",
+    r"WIFI:S:campus;T:WPA2-EAP;P:password;E:PEAP;PH2:MSCHAPV2;I:alice\@corp;A:anonymous;;",
+    "WIFI:S:campus;T:WPA2-EAP;P:password;;",
+    "WIFI:S:campus;T:WPA2-EAP;E:FAST;;",
+    "WIFI:S:campus;T:WPA2-EAP;E:TTLS;PH2:CHAP;;",
+    "WIFI:S:lobby;T:SAE;P:a much longer WPA3 passphrase than WPA2 allows, which is fine for SAE;R:1;;",
+    "WIFI:S:lobby;T:SAE;P:;;",
+    "WIFI:S:lobby;T:WPA;P:password;R:x;;",
+    "WIFI:S:lobby;T:OWE;P:;;",
+    "WIFI:S:lobby;T:OWE;P:password;;",
+    "MECARD:N:Alice;;",
+];
+
+/// Removes the backslash escapes added by [`mecardify`].
+///
+/// Any character may follow a backslash. A trailing backslash with nothing to escape is an error, and so is a
+/// string longer than [`Wifi::MAX_MECARD_LEN`].
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{mecard_unescape, mecardify, ParseError};
+///
+/// assert_eq!(mecard_unescape("Example\\:SSID").unwrap(), "Example:SSID");
+/// assert_eq!(mecard_unescape(&mecardify("A;B,C\\D")).unwrap(), "A;B,C\\D");
+/// assert_eq!(mecard_unescape("dangling\\"), Err(ParseError::DanglingEscape));
+/// ```
+pub fn mecard_unescape(s: &str) -> Result<String, ParseError> {
+    if s.len() > Wifi::MAX_MECARD_LEN {
+        return Err(ParseError::TooLong { bytes: s.len() });
+    }
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            unescaped.push(chars.next().ok_or(ParseError::DanglingEscape)?);
+        } else {
+            unescaped.push(c);
+        }
+    }
+    Ok(unescaped)
 }
 
 /// Escapes special characters for the MECARD-like syntax.
 ///
-/// The four characters `:`, `;`, `,`, and `\` are escaped with a backslash.
+/// The four characters `:`, `;`, `,`, and `\` are escaped with a backslash. Strings without any of them, which
+/// is most SSIDs and passwords, are borrowed rather than copied.
 ///
 /// # Example
 ///
 /// ```
+/// use std::borrow::Cow;
 /// use qrfi::mecardify;
 ///
 /// assert_eq!(mecardify("Example:SSID"), "Example\\:SSID");
 /// assert_eq!(mecardify("A;B,C\\D"), "A\\;B\\,C\\\\D");
+/// assert!(matches!(mecardify("guest"), Cow::Borrowed("guest")));
 /// ```
-pub fn mecardify(s: &str) -> String {
-    let mut mecardified = String::new();
+pub fn mecardify(s: &str) -> Cow<'_, str> {
+    if !s.contains([',', ':', ';', '\\']) {
+        return Cow::Borrowed(s);
+    }
+    Cow::Owned(write_to_string(|out| write_escaped(out, s)))
+}
+
+/// Collects what `write` writes into a `String` of exactly that length.
+///
+/// The buffer is sized up front so it never reallocates, which would leave copies of a password behind in freed
+/// memory. Writing to a `String` never fails, so the results of `write` are dropped.
+fn write_to_string(write: impl Fn(&mut dyn core::fmt::Write) -> core::fmt::Result) -> String {
+    let mut len = ByteCount(0);
+    let _ = write(&mut len);
+    let mut out = String::with_capacity(len.0);
+    let _ = write(&mut out);
+    out
+}
+
+/// Counts the bytes written to it, for sizing a buffer before a secret is copied into it.
+struct ByteCount(usize);
+
+impl core::fmt::Write for ByteCount {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0 = self.0.saturating_add(s.len());
+        Ok(())
+    }
+}
+
+/// Writes `s` to `out`, escaped like [`mecardify`].
+fn write_escaped<W: core::fmt::Write + ?Sized>(out: &mut W, s: &str) -> core::fmt::Result {
     for c in s.chars() {
         if matches!(c, ',' | ':' | ';' | '\\' ) {
-            mecardified.push('\\');
+            out.write_char('\\')?;
+        }
+        out.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// Writes `bytes` to `out` as the WPA3 URI's values: printable ASCII except `;` and `%` as is, other bytes as
+/// `%XX`.
+fn write_percent_encoded<W: core::fmt::Write + ?Sized>(out: &mut W, bytes: &[u8]) -> core::fmt::Result {
+    for &byte in bytes {
+        match byte {
+            b';' | b'%' | ..0x20 | 0x7f.. => write!(out, "%{:02X}", byte)?,
+            _ => out.write_char(char::from(byte))?,
         }
-        mecardified.push(c);
     }
-    mecardified
+    Ok(())
+}
+
+/// Whether a reader could take `s` for a hex-encoded value.
+fn is_hex_lookalike(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Writes `s` to `out` escaped, and wrapped in double quotes if it looks like hex.
+fn write_quoted<W: core::fmt::Write + ?Sized>(out: &mut W, s: &str) -> core::fmt::Result {
+    if is_hex_lookalike(s) {
+        write!(out, "\"{}\"", s)
+    } else {
+        write_escaped(out, s)
+    }
+}
+
+/// Decodes an unquoted SSID made only of an even number of hex digits, as [`Ssid::write_hex`] writes it.
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !is_hex_lookalike(s) || !s.len().is_multiple_of(2) {
+        return None;
+    }
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| core::str::from_utf8(pair).ok().and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
+
+/// Removes the double quotes [`write_quoted`] adds around hex lookalikes.
+fn unquote(s: String) -> String {
+    match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(inner) if is_hex_lookalike(inner) => inner.to_string(),
+        _ => s,
+    }
+}
+
+/// How strictly SSIDs and passwords are checked.
+///
+/// Real-world access points accept some values the standards rule out, such as passphrases outside printable
+/// ASCII. Relax the checks only to produce codes for setups known to work. [`Wifi::from_mecard`] and
+/// deserializing always validate strictly.
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{ValidationPolicy, WifiBuilder};
+///
+/// assert!(WifiBuilder::new("lobby").wpa("Passwört!").build().is_err());
+/// assert!(WifiBuilder::new("lobby").wpa("Passwört!").policy(ValidationPolicy::Lenient).build().is_ok());
+/// assert!(WifiBuilder::new("").policy(ValidationPolicy::Off).build().is_ok());
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum ValidationPolicy {
+    /// Only values the standards allow.
+    #[default]
+    Strict,
+    /// Also WPA passphrases of 8-63 bytes with any UTF-8 characters, and 152-bit and 256-bit WEP keys of 16 or 29
+    /// characters or 32 or 58 hex digits. SSIDs keep the 1-32 byte limit of 802.11.
+    Lenient,
+    /// No checks at all.
+    Off,
+}
+
+/// Layouts of the `WIFI:` string for readers that disagree on field order, optional fields, and the end of the
+/// payload. Every layout parses with [`Wifi::from_mecard`].
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{CompatProfile, WifiBuilder};
+///
+/// let wifi = WifiBuilder::new("SSID").build().unwrap();
+/// assert_eq!(wifi.to_mecard(), "WIFI:S:SSID;T:nopass;P:;H:false;;");
+/// assert_eq!(wifi.with_compat_profile(CompatProfile::Loose).to_mecard(), "WIFI:S:SSID;T:nopass;");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum CompatProfile {
+    /// `S`, `T`, `P`, and `H` in ZXing's order, each always written, ended by `;;`.
+    #[default]
+    Zxing,
+    /// `T` first, the order most iOS guides use, without `H:false` or an empty `P:`. iOS Camera ignores `H:` anyway.
+    Ios,
+    /// ZXing's order without `H:false`, since readers take a missing `H:` for a broadcast network.
+    Android,
+    /// Only the fields that carry information, ended by a single `;`, for the shortest payload tolerant readers take.
+    Loose,
 }
 
 /// Supported Wi-Fi authentication types.
@@ -184,26 +1183,83 @@ pub fn mecardify(s: &str) -> String {
 /// assert_eq!(default_auth, AuthType::Wpa);
 /// assert_eq!(format!("{}", default_auth), "WPA");
 /// ```
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Default)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AuthType {
     /// WEP (Wired Equivalent Privacy).
-    #[value(name = "WEP")]
+    #[cfg_attr(feature = "cli", value(name = "WEP"))]
+    #[cfg_attr(feature = "serde", serde(rename = "WEP"))]
     Wep,
     /// WPA, WPA2, or WPA3 (Wi-Fi Protected Access).
     #[default]
-    #[value(name = "WPA")]
+    #[cfg_attr(feature = "cli", value(name = "WPA", aliases = ["wpa2", "wpa3", "wpa-psk", "wpa2-psk"]))]
+    #[cfg_attr(feature = "serde", serde(rename = "WPA"))]
     Wpa,
     /// No password required (Open network).
-    #[value(name = "nopass")]
+    #[cfg_attr(feature = "cli", value(name = "nopass", aliases = ["open", "none"]))]
+    #[cfg_attr(feature = "serde", serde(rename = "nopass"))]
     Nopass,
+    /// WPA3 Personal only (Simultaneous Authentication of Equals).
+    #[cfg_attr(feature = "cli", value(name = "SAE", alias = "wpa3-sae"))]
+    #[cfg_attr(feature = "serde", serde(rename = "SAE"))]
+    Sae,
+    /// WPA2-Enterprise (802.1X), configured with an [`Enterprise`].
+    #[cfg_attr(feature = "cli", value(name = "WPA2-EAP", aliases = ["wpa-eap", "eap"]))]
+    #[cfg_attr(feature = "serde", serde(rename = "WPA2-EAP"))]
+    Wpa2Eap,
+    /// Wi-Fi Enhanced Open: no password, but traffic is encrypted (Opportunistic Wireless Encryption).
+    #[cfg_attr(feature = "cli", value(name = "OWE", alias = "enhanced-open"))]
+    #[cfg_attr(feature = "serde", serde(rename = "OWE"))]
+    Owe,
 }
-impl std::fmt::Display for AuthType {
+impl core::fmt::Display for AuthType {
     /// Formats the authentication type for display.
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
             AuthType::Wep => write!(f, "WEP"),
             AuthType::Wpa => write!(f, "WPA"),
             AuthType::Nopass => write!(f, "nopass"),
+            AuthType::Sae => write!(f, "SAE"),
+            AuthType::Wpa2Eap => write!(f, "WPA2-EAP"),
+            AuthType::Owe => write!(f, "OWE"),
         }
     }
 }
+impl core::str::FromStr for AuthType {
+    type Err = ParseError;
+
+    /// Parses an authentication type, ignoring case and accepting common aliases.
+    ///
+    /// `wpa`, `wpa2`, `wpa3`, `wpa-psk`, and `wpa2-psk` are WPA; `sae` and `wpa3-sae` are SAE; `wep` is WEP;
+    /// `wpa2-eap`, `wpa-eap`, and `eap` are WPA2-Enterprise; `owe` and `enhanced-open` are OWE; `nopass`, `open`,
+    /// `none`, and the empty string are open networks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::AuthType;
+    ///
+    /// assert_eq!("WPA2".parse(), Ok(AuthType::Wpa));
+    /// assert_eq!("open".parse(), Ok(AuthType::Nopass));
+    /// assert!("WPA4".parse::<AuthType>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "wpa" | "wpa2" | "wpa3" | "wpa-psk" | "wpa2-psk" => Ok(AuthType::Wpa),
+            "sae" | "wpa3-sae" => Ok(AuthType::Sae),
+            "wep" => Ok(AuthType::Wep),
+            "wpa2-eap" | "wpa-eap" | "eap" => Ok(AuthType::Wpa2Eap),
+            "owe" | "enhanced-open" => Ok(AuthType::Owe),
+            "nopass" | "open" | "none" | "" => Ok(AuthType::Nopass),
+            _ => Err(ParseError::UnknownAuthType(s.to_string())),
+        }
+    }
+}
+impl TryFrom<&str> for AuthType {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
@@ -0,0 +1,59 @@
+/// Problems that do not make a Wi-Fi configuration invalid but are likely mistakes.
+///
+/// Each lint has a stable [`Lint::code`] that scripts can match on or suppress; the `Display` output is a
+/// human-readable message suitable for the CLI.
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{Lint, WifiBuilder};
+///
+/// let wifi = WifiBuilder::new("Guest ").wep("12345").build().unwrap();
+/// assert_eq!(wifi.lints(), [Lint::SsidWhitespace, Lint::Wep]);
+/// assert_eq!(Lint::Wep.code(), "wep");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum Lint {
+    /// The SSID starts or ends with whitespace.
+    SsidWhitespace,
+    /// The SSID contains control characters.
+    SsidControlCharacter,
+    /// The SSID is not UTF-8, so the payload carries it in hex.
+    SsidNotUtf8,
+    /// The password starts or ends with a space.
+    PasswordWhitespace,
+    /// The WPA passphrase has characters outside ASCII, such as accented letters or emoji, which only
+    /// [`ValidationPolicy::Lenient`](crate::ValidationPolicy::Lenient) lets through.
+    PasswordNotAscii,
+    /// The network uses WEP, which can be cracked in minutes.
+    Wep,
+}
+impl Lint {
+    /// A short identifier that stays the same across releases, such as `ssid-whitespace`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Lint::SsidWhitespace => "ssid-whitespace",
+            Lint::SsidControlCharacter => "ssid-control-character",
+            Lint::SsidNotUtf8 => "ssid-not-utf8",
+            Lint::PasswordWhitespace => "password-whitespace",
+            Lint::PasswordNotAscii => "password-not-ascii",
+            Lint::Wep => "wep",
+        }
+    }
+}
+impl core::fmt::Display for Lint {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Lint::SsidWhitespace => write!(f, "SSID starts or ends with whitespace, which is easy to miss when checking it."),
+            Lint::SsidControlCharacter => write!(f, "SSID contains control characters, which many readers drop or mangle."),
+            Lint::SsidNotUtf8 => write!(f, "SSID is not UTF-8; readers that do not decode hex SSIDs will show digits instead."),
+            Lint::PasswordWhitespace => write!(f, "Password starts or ends with a space, which is easy to lose when typing it."),
+            Lint::PasswordNotAscii => write!(
+                f,
+                "WPA passphrase has characters outside ASCII; devices that cannot type them or normalize them differently will not connect."
+            ),
+            Lint::Wep => write!(f, "WEP is insecure; switch the network to WPA2 or WPA3 if you can."),
+        }
+    }
+}
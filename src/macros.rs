@@ -0,0 +1,168 @@
+/// Builds the `WIFI:` string of a fixed network at compile time, as a `&'static str`.
+///
+/// Takes the SSID, and optionally a WPA passphrase or key and whether the network is hidden; an empty password
+/// makes the network open. All arguments must be constants. The escaping is the same as [`Wifi::to_mecard`]'s,
+/// and invalid values fail to compile with the message [`ValidationError`] would give.
+///
+/// This suits firmware that bakes a provisioning code into its image and cannot afford an allocator at runtime.
+///
+/// [`Wifi::to_mecard`]: crate::Wifi::to_mecard
+/// [`ValidationError`]: crate::ValidationError
+///
+/// # Example
+///
+/// ```
+/// const GUEST: &str = qrfi::mecard!("Guest;Lobby", "PASSWORD");
+/// assert_eq!(GUEST, r"WIFI:S:Guest\;Lobby;T:WPA;P:PASSWORD;H:false;;");
+/// assert_eq!(qrfi::mecard!("cafe", "", true), r#"WIFI:S:"cafe";T:nopass;P:;H:true;;"#);
+/// ```
+///
+/// ```compile_fail
+/// const TOO_SHORT: &str = qrfi::mecard!("SSID", "short");
+/// ```
+#[macro_export]
+macro_rules! mecard {
+    ($ssid:expr $(,)?) => {
+        $crate::mecard!($ssid, "", false)
+    };
+    ($ssid:expr, $password:expr $(,)?) => {
+        $crate::mecard!($ssid, $password, false)
+    };
+    ($ssid:expr, $password:expr, $hidden:expr $(,)?) => {{
+        const SSID: &str = $ssid;
+        const PASSWORD: &str = $password;
+        const HIDDEN: bool = $hidden;
+        const PAYLOAD: &str = {
+            if let Some(message) = $crate::__private::check(SSID, PASSWORD) {
+                panic!("{}", message);
+            }
+            const LEN: usize = $crate::__private::len(SSID, PASSWORD, HIDDEN);
+            const BYTES: [u8; LEN] = $crate::__private::write(SSID, PASSWORD, HIDDEN);
+            match ::core::str::from_utf8(&BYTES) {
+                Ok(payload) => payload,
+                Err(_) => panic!("escaping produced invalid UTF-8"),
+            }
+        };
+        PAYLOAD
+    }};
+}
+
+/// The `const fn`s behind [`mecard!`]. They are public only so the macro can reach them.
+#[doc(hidden)]
+pub mod __private {
+    /// The message of the first [`ValidationError`](crate::ValidationError) the values would cause, if any.
+    pub const fn check(ssid: &str, password: &str) -> Option<&'static str> {
+        if ssid.is_empty() {
+            return Some("SSID cannot be empty.");
+        }
+        if ssid.len() > 32 {
+            return Some("SSID is too long. It must be between 1 and 32 bytes.");
+        }
+        let len = password.len();
+        let is_key = len == 64 && is_hex(password);
+        let is_passphrase = len >= 8 && len <= 63 && is_printable(password);
+        if len > 0 && !is_key && !is_passphrase {
+            return Some("WPA passphrase must be 8-63 printable ASCII characters, or 64 hex digits.");
+        }
+        None
+    }
+
+    /// Length of the payload [`write`] produces.
+    pub const fn len(ssid: &str, password: &str, hidden: bool) -> usize {
+        let auth = if password.is_empty() { "nopass".len() } else { "WPA".len() };
+        let hidden = if hidden { "true".len() } else { "false".len() };
+        "WIFI:S:;T:;P:;H:;;".len() + quoted_len(ssid, true) + auth + quoted_len(password, password.len() != 64) + hidden
+    }
+
+    /// Writes the payload into an array of [`len`] bytes; a shorter array gets a truncated payload.
+    pub const fn write<const N: usize>(ssid: &str, password: &str, hidden: bool) -> [u8; N] {
+        let mut out = Writer { bytes: [0; N], pos: 0 };
+        out = out.raw("WIFI:S:").quoted(ssid, true).raw(";T:");
+        out = out.raw(if password.is_empty() { "nopass" } else { "WPA" });
+        out = out.raw(";P:").quoted(password, password.len() != 64);
+        out = out.raw(if hidden { ";H:true;;" } else { ";H:false;;" });
+        out.bytes
+    }
+
+    /// Length of `s` once escaped, plus two for the double quotes around hex lookalikes if `quote` is set.
+    const fn quoted_len(s: &str, quote: bool) -> usize {
+        let mut len = s.len();
+        let mut rest = s.as_bytes();
+        while let [byte, tail @ ..] = rest {
+            if is_special(*byte) {
+                len += 1;
+            }
+            rest = tail;
+        }
+        if quote && is_hex(s) { len + 2 } else { len }
+    }
+
+    struct Writer<const N: usize> {
+        bytes: [u8; N],
+        pos: usize,
+    }
+    impl<const N: usize> Writer<N> {
+        const fn push(mut self, byte: u8) -> Self {
+            if self.pos < N {
+                #[allow(clippy::indexing_slicing)] // Checked against N just above.
+                {
+                    self.bytes[self.pos] = byte;
+                }
+                self.pos += 1;
+            }
+            self
+        }
+
+        const fn raw(mut self, s: &str) -> Self {
+            let mut rest = s.as_bytes();
+            while let [byte, tail @ ..] = rest {
+                self = self.push(*byte);
+                rest = tail;
+            }
+            self
+        }
+
+        /// Escapes like `mecardify` and, if `quote` is set, wraps hex lookalikes in double quotes.
+        const fn quoted(mut self, s: &str, quote: bool) -> Self {
+            let quote = quote && is_hex(s);
+            if quote {
+                self = self.push(b'"');
+            }
+            let mut rest = s.as_bytes();
+            while let [byte, tail @ ..] = rest {
+                if is_special(*byte) {
+                    self = self.push(b'\\');
+                }
+                self = self.push(*byte);
+                rest = tail;
+            }
+            if quote { self.push(b'"') } else { self }
+        }
+    }
+
+    const fn is_special(byte: u8) -> bool {
+        matches!(byte, b',' | b':' | b';' | b'\\')
+    }
+
+    const fn is_hex(s: &str) -> bool {
+        let mut rest = s.as_bytes();
+        while let [byte, tail @ ..] = rest {
+            if !byte.is_ascii_hexdigit() {
+                return false;
+            }
+            rest = tail;
+        }
+        !s.is_empty()
+    }
+
+    const fn is_printable(s: &str) -> bool {
+        let mut rest = s.as_bytes();
+        while let [byte, tail @ ..] = rest {
+            if *byte < 0x20 || *byte > 0x7e {
+                return false;
+            }
+            rest = tail;
+        }
+        true
+    }
+}
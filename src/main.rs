@@ -1,10 +1,25 @@
-use clap::{Parser, ValueEnum};
-use qrcode::render::unicode;
-use qrcode::QrCode;
-use std::io::{self, Read, Write, Cursor, IsTerminal};
-use image::{Luma, ImageBuffer, ImageFormat};
+use clap::{CommandFactory, FromArgMatches, Parser, ValueEnum};
+use std::fs::{self, File};
+use std::io::{self, Read, Write, Cursor, IsTerminal, BufWriter};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use sha2::{Digest, Sha256};
+use flate2::{write::GzEncoder, Compression};
+use image::{Rgb as Pixel, ImageBuffer, ImageFormat};
 
-use qrfi::{Wifi, Ssid, Password, AuthType};
+use qrfi::{Wifi, Ssid, Password, AuthType, CompatProfile, ValidationPolicy, EapMethod, Enterprise, Phase2, Colors, ErrorCorrection, QrMatrix, RenderOptions, Rgb, write_svg};
+use qrfi::{AsciiRenderer, Renderer, SvgRenderer};
+
+use audit::HashWriter;
+
+mod audit;
+mod compat;
+mod explain;
+mod export;
+mod policy;
+mod scan;
+mod snippet;
+mod wizard;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Default)]
 enum Format {
@@ -12,6 +27,111 @@ enum Format {
     Ascii,
     Png,
     Svg,
+    Svgz,
+    Escpos,
+    Zpl,
+    Gif,
+    /// GIF with a slowly pulsing border outside the quiet zone, for digital signage.
+    #[value(name = "gif-animated")]
+    GifAnimated,
+    #[cfg(feature = "avif")]
+    Avif,
+}
+
+/// Brother P-touch tape widths, printed at 180 dpi.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum Tape {
+    #[value(name = "9mm")]
+    Mm9,
+    #[value(name = "12mm")]
+    Mm12,
+    #[value(name = "18mm")]
+    Mm18,
+    #[value(name = "24mm")]
+    Mm24,
+}
+impl Tape {
+    /// Printable height of the tape in dots.
+    fn printable_dots(self) -> u32 {
+        match self {
+            Tape::Mm9 => 50,
+            Tape::Mm12 => 70,
+            Tape::Mm18 => 112,
+            Tape::Mm24 => 128,
+        }
+    }
+}
+
+/// Sizing presets for common destinations.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum Preset {
+    /// 50 mm sticker printed at 300 dpi.
+    #[value(name = "sticker-50mm")]
+    Sticker50mm,
+    /// 150 mm code centered on an A4 poster, printed at 300 dpi.
+    #[value(name = "poster-a4")]
+    PosterA4,
+    /// Two thirds of the height of a 1920x1080 slide.
+    #[value(name = "slide-1080p")]
+    Slide1080p,
+    /// Small square icon with a minimal quiet zone.
+    Favicon,
+}
+impl Preset {
+    /// Target edge length in pixels, print resolution, and quiet zone in modules.
+    fn sizing(self) -> (u32, u32, u32) {
+        match self {
+            Preset::Sticker50mm => (590, 300, 4),
+            Preset::PosterA4 => (1772, 300, 4),
+            Preset::Slide1080p => (720, 96, 4),
+            Preset::Favicon => (128, 96, 1),
+        }
+    }
+}
+
+/// Color schemes written side by side with `--variants`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum Variant {
+    /// The configured colors, for light backgrounds.
+    Light,
+    /// The configured colors swapped, for dark backgrounds. Some older scanners cannot read inverted codes.
+    Dark,
+    /// Pure black on white.
+    HighContrast,
+}
+impl Variant {
+    fn colors(self, colors: Colors) -> Colors {
+        match self {
+            Variant::Light => colors,
+            Variant::Dark => Colors { dark: colors.light, light: colors.dark },
+            Variant::HighContrast => Colors::default(),
+        }
+    }
+
+    /// Suffix added to the output file name, e.g. `qr-dark.png`.
+    fn suffix(self) -> &'static str {
+        match self {
+            Variant::Light => "light",
+            Variant::Dark => "dark",
+            Variant::HighContrast => "high-contrast",
+        }
+    }
+}
+
+/// Payload formats of the `WIFI:` string.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Default)]
+enum Dialect {
+    /// ZXing's MECARD-like syntax, which nearly every reader understands.
+    #[default]
+    Mecard,
+    /// The percent-encoded URI of the WPA3 specification.
+    #[value(name = "wifi-uri")]
+    WifiUri,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum Checksum {
+    Sha256,
 }
 
 #[derive(Parser, Debug)]
@@ -32,70 +152,807 @@ enum Format {
 struct Args {
     #[arg(help = "SSID of the Wi-Fi network (or via stdin)")]
     ssid: Option<String>,
-    #[arg(short = 't', long, value_enum, default_value_t = AuthType::Wpa, help = "Wi-Fi Authentication type")]
+    #[arg(short = 't', long, value_enum, ignore_case = true, default_value_t = AuthType::Wpa, help = "Wi-Fi Authentication type")]
     authentication_type: AuthType,
     #[arg(short = 'p', long, help = "Wi-Fi password (ignored if authentication-type is 'nopass')")]
     password: Option<String>,
     #[arg(short = 'H', long, default_value_t = false, help = "Option to specify when SSID is hidden")]
     hidden: bool,
+    #[arg(long, default_value_t = false, help = "Label SAE networks T:WPA for readers that do not know T:SAE")]
+    sae_as_wpa: bool,
+    #[arg(long, default_value_t = false, help = "Add the WPA3 transition disable indicator R:1 (WPA and SAE only)")]
+    transition_disable: bool,
+    #[arg(long, value_enum, help = "EAP method of a WPA2-EAP network")]
+    eap: Option<EapMethod>,
+    #[arg(long, requires = "eap", help = "User name for a WPA2-EAP network")]
+    identity: Option<String>,
+    #[arg(long, requires = "eap", help = "Outer identity sent before the tunnel is set up (WPA2-EAP)")]
+    anonymous_identity: Option<String>,
+    #[arg(long, value_enum, requires = "eap", help = "Inner authentication of PEAP or TTLS (WPA2-EAP)")]
+    phase2: Option<Phase2>,
     #[arg(short = 'f', long, value_enum, default_value_t = Format::Ascii, help = "Output format")]
     format: Format,
+    #[arg(short = 'e', long, value_enum, default_value_t = ErrorCorrection::M, help = "Error correction level")]
+    error_correction: ErrorCorrection,
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..), help = "Pixels per module (png, svg, gif)")]
+    scale: u32,
+    #[arg(long, default_value_t = 4, help = "Quiet zone around the code in modules")]
+    margin: u32,
+    #[arg(long, default_value_t = Rgb::BLACK, help = "Color of dark modules as #rrggbb (png, svg, gif)")]
+    dark_color: Rgb,
+    #[arg(long, default_value_t = Rgb::WHITE, help = "Color of light modules as #rrggbb (png, svg, gif)")]
+    light_color: Rgb,
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(i16).range(1..=40), help = "Warn when the QR code needs a version above this")]
+    max_version: i16,
+    #[arg(long, default_value_t = false, help = "Turn warnings into errors")]
+    strict: bool,
+    #[arg(long, default_value_t = 300, value_parser = clap::value_parser!(u32).range(1..), help = "Print resolution used to estimate the physical module size and to size --label-width")]
+    dpi: u32,
+    #[arg(long, default_value_t = false, help = "Print a 0-100 scanability score with suggestions instead of the QR code")]
+    score: bool,
+    #[arg(long, value_parser = scan::parse_distance, help = "Expected scan distance, e.g. 50cm or 3m [default for reports: 20cm]")]
+    scan_distance: Option<f64>,
+    #[arg(long, default_value_t = false, help = "Print a pass/fail report against QR print guidelines instead of the QR code")]
+    compliance_report: bool,
+    #[arg(long, default_value_t = false, help = "Print known reader compatibility caveats for this network instead of the QR code")]
+    compat_report: bool,
+    #[arg(short = 'o', long, help = "Write the code to this file instead of stdout")]
+    output: Option<PathBuf>,
+    #[arg(long, value_enum, requires = "output", help = "Write a checksum sidecar file next to the output")]
+    checksum: Option<Checksum>,
+    #[arg(long, value_name = "SECRET_KEY", requires = "output", help = "Sign the output with minisign using this secret key")]
+    minisign_key: Option<PathBuf>,
+    #[arg(long, value_enum, help = "Size a monochrome PNG to fit a Brother P-touch tape (overrides --scale and colors)")]
+    tape: Option<Tape>,
+    #[arg(long, value_name = "WIDTH", value_parser = scan::parse_distance, help = "Center a ZPL code on a label this wide at --dpi, e.g. 100mm (overrides --scale)")]
+    label_width: Option<f64>,
+    #[arg(long, value_name = "DIR", conflicts_with = "output", help = "Write PNG, SVG, alt text, and a credentials card into a directory")]
+    bundle: Option<PathBuf>,
+    #[arg(long, value_enum, conflicts_with_all = ["scale", "margin", "dpi", "tape", "label_width"], help = "Sizing preset that sets scale, margin, and dpi")]
+    preset: Option<Preset>,
+    #[arg(long, default_value_t = false, help = "Keep stdout for the output only and print diagnostics as qrfi:<level>:<code>:<message>")]
+    porcelain: bool,
+    #[arg(long, default_value_t = false, help = "Only validate the arguments; exit without generating the QR code")]
+    check: bool,
+    #[arg(short = 'i', long, default_value_t = false, help = "Ask for the SSID, authentication type, password, and hidden flag line by line")]
+    interactive: bool,
+    #[arg(long, value_name = "FILE", help = "Enforce an organization policy file (TOML)")]
+    policy: Option<PathBuf>,
+    #[arg(long, value_name = "BYTES", default_value_t = 65536, help = "Refuse an SSID on stdin, an interactive answer, or a policy file larger than this")]
+    max_input_bytes: u64,
+    #[arg(long, value_name = "FILE", help = "Append a JSON line per generated file to this audit log (never includes the password or hashes of anything derived from it)")]
+    audit_log: Option<PathBuf>,
+    #[arg(long, value_enum, value_delimiter = ',', help = "Also write provisioning scripts for devices without a camera")]
+    export: Vec<export::Export>,
+    #[arg(long, value_name = "DIR", default_value = ".", help = "Directory for --export files")]
+    export_dir: PathBuf,
+    #[arg(long, value_name = "TEXT", help = "Draw faint diagonal text behind the code (svg, svgz)")]
+    watermark: Option<String>,
+    #[arg(long, default_value_t = false, conflicts_with = "export", help = "Pixelate the code and mask the password, for sharing layout proofs")]
+    redacted: bool,
+    #[arg(long, value_enum, value_delimiter = ',', requires = "output", help = "Write these color variants next to --output with suffixed file names")]
+    variants: Vec<Variant>,
+    #[arg(long, default_value_t = false, help = "Draw dark modules as outlines to save ink on draft prints (scans less reliably)")]
+    outline: bool,
+    #[arg(long, value_name = "TEXT", help = "Print text in the bottom margin outside the quiet zone (svg, svgz)")]
+    label: Option<String>,
+    #[arg(long, value_name = "HEX", conflicts_with_all = ["ssid", "interactive"], help = "SSID as hex bytes, for SSIDs that are not UTF-8")]
+    ssid_hex: Option<String>,
+    #[arg(long, default_value_t = false, help = "Write the SSID to the payload in hex, for readers that mangle escaped characters")]
+    hex_ssid: bool,
+    #[arg(long, value_enum, default_value_t = Dialect::Mecard, help = "Payload format")]
+    dialect: Dialect,
+    #[arg(long, value_enum, default_value_t = CompatProfile::Zxing, help = "Field order and optional fields of the payload, for readers that disagree on them")]
+    compat: CompatProfile,
+    #[arg(long, value_enum, value_name = "SHELL", help = "Print a wifi-qr shell function that runs qrfi with the other options given here as defaults")]
+    prompt_snippet: Option<snippet::Shell>,
+    #[arg(long, value_enum, default_value_t = ValidationPolicy::Strict, help = "How strictly to check the SSID and password; lenient and off allow values that only some access points accept")]
+    validation: ValidationPolicy,
+    #[arg(long, default_value_t = false, help = "Encode the WPA key derived from the passphrase instead of the passphrase itself")]
+    emit_psk: bool,
+    #[arg(long, default_value_t = false, help = "Explain each field of the payload on stderr (the password stays masked)")]
+    explain: bool,
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut args = Args::parse();
-    if args.ssid.is_none() && !io::stdin().is_terminal() {
-        let mut buffer = String::new();
-        io::stdin().read_to_string(&mut buffer)?;
-        args.ssid = Some(buffer.trim_end_matches(['\n', '\r']).to_string());
+/// Edge length in modules of the blocks a redacted code is pixelated into.
+const REDACT_BLOCK: usize = 3;
+
+/// Pixelates the code so it keeps its size and look but no longer decodes.
+///
+/// Each block becomes entirely dark or light depending on which color the majority of its modules has.
+fn pixelate(modules: &mut [bool], width: usize) {
+    for by in (0..width).step_by(REDACT_BLOCK) {
+        for bx in (0..width).step_by(REDACT_BLOCK) {
+            let cells: Vec<usize> = (by..(by + REDACT_BLOCK).min(width))
+                .flat_map(|y| (bx..(bx + REDACT_BLOCK).min(width)).map(move |x| y * width + x))
+                .collect();
+            let dark = cells.iter().filter(|&&i| modules[i]).count() * 2 > cells.len();
+            for i in cells {
+                modules[i] = dark;
+            }
+        }
     }
-    let ssid = Ssid::new(args.ssid.unwrap_or_default())?;
-    let password = Password::new(args.password, args.authentication_type)?;
-    let wifi = Wifi::new(ssid, password, args.hidden);
-    let mecard = wifi.to_mecard();
-    let code = QrCode::new(&mecard)?;
-    match args.format {
-        Format::Ascii => {
-            let image = code.render::<unicode::Dense1x2>()
-                .dark_color(unicode::Dense1x2::Dark)
-                .light_color(unicode::Dense1x2::Light)
-                .build();
-            println!("{}", image);
-        }
-        Format::Png => {
-            let width = code.width() as u32;
-            let quiet_zone = 4;
-            let total_width = width + (quiet_zone * 2);
-            let scale = 10;
-            let final_dim = total_width * scale;
-            let mut img: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::new(final_dim, final_dim);
-            for pixel in img.pixels_mut() {
-                *pixel = Luma([255]);
+}
+
+/// ASCII and SVG come from the library; the other formats need dependencies only the binary has.
+impl Renderer for Format {
+    fn render(&self, code: &QrMatrix, options: &RenderOptions, out: &mut dyn Write) -> Result<(), Box<dyn std::error::Error>> {
+        // Only the SVG writer draws text, and a draft must not come out looking final.
+        if options.watermark.is_some() && !matches!(self, Format::Svg | Format::Svgz) {
+            return Err("--watermark requires --format svg or svgz.".into());
+        }
+        let modules = code.modules();
+        match self {
+            Format::Ascii => AsciiRenderer.render(code, options, out)?,
+            Format::Png => {
+                let mut buf = Cursor::new(Vec::new());
+                raster(modules, code.width(), options)?.write_to(&mut buf, ImageFormat::Png)?;
+                out.write_all(buf.get_ref())?;
+            }
+            #[cfg(feature = "avif")]
+            Format::Avif => {
+                // Quality 100 keeps the two colors exact enough that module edges stay sharp.
+                let encoder = image::codecs::avif::AvifEncoder::new_with_speed_quality(out, 4, 100);
+                raster(modules, code.width(), options)?.write_with_encoder(encoder)?;
+            }
+            Format::Svg => SvgRenderer.render(code, options, out)?,
+            Format::Svgz => {
+                let mut gz = GzEncoder::new(out, Compression::default());
+                write_svg(modules, code.width(), options, &mut gz)?;
+                gz.finish()?;
+            }
+            Format::Escpos => {
+                let (dim, bitmap) = bitmap(modules, code.width(), options)?;
+                let row_bytes = dim.div_ceil(8);
+                if row_bytes > 0xffff || dim > 0xffff {
+                    return Err("Code is too large for an ESC/POS raster image.".into());
+                }
+                // ESC @ (initialize), ESC a 1 (center), GS v 0 (raster bit image, normal density)
+                out.write_all(b"\x1b@\x1ba\x01\x1dv0\x00")?;
+                out.write_all(&[row_bytes as u8, (row_bytes >> 8) as u8, dim as u8, (dim >> 8) as u8])?;
+                out.write_all(&bitmap)?;
+                // Feed three lines, then GS V 66 0 (feed to the cutter and cut partially)
+                out.write_all(b"\n\n\n\x1dVB\x00")?;
             }
-            for (y, row) in code.to_colors().chunks(width as usize).enumerate() {
-                for (x, color) in row.iter().enumerate() {
-                    if color == &qrcode::types::Color::Dark {
-                        let px = (x as u32 + quiet_zone) * scale;
-                        let py = (y as u32 + quiet_zone) * scale;
-                        for dx in 0..scale {
-                            for dy in 0..scale {
-                                img.put_pixel(px + dx, py + dy, Luma([0]));
-                            }
-                        }
+            Format::Gif => {
+                let (dim, pixels) = indexed(modules, code.width(), options)?;
+                let dim = u16::try_from(dim).map_err(|_| "Code is too large for a GIF image.")?;
+                let mut encoder = gif::Encoder::new(out, dim, dim, &palette(options.colors))?;
+                encoder.write_frame(&gif::Frame::from_indexed_pixels(dim, dim, pixels, None))?;
+                encoder.into_inner()?;
+            }
+            Format::GifAnimated => {
+                let (dim, pixels) = indexed(modules, code.width(), options)?;
+                // The border is added outside the quiet zone, so the code and its margin are the same in every frame.
+                let border = ATTENTION_BORDER * options.scale as usize;
+                let full = dim + border * 2;
+                let full16 = u16::try_from(full).map_err(|_| "Code is too large for a GIF image.")?;
+                let mut palette = palette(options.colors).to_vec();
+                for frame in 0..ATTENTION_FRAMES {
+                    let phase = frame as f64 / ATTENTION_FRAMES as f64 * std::f64::consts::TAU;
+                    let Rgb(r, g, b) = blend(options.colors.light, options.colors.dark, (1.0 - phase.cos()) / 2.0 * ATTENTION_STRENGTH);
+                    palette.extend([r, g, b]);
+                }
+                let mut encoder = gif::Encoder::new(out, full16, full16, &palette)?;
+                encoder.set_repeat(gif::Repeat::Infinite)?;
+                for frame in 0..ATTENTION_FRAMES {
+                    let mut frame_pixels = vec![2 + frame as u8; full * full];
+                    for (y, row) in pixels.chunks(dim.max(1)).enumerate() {
+                        frame_pixels[(y + border) * full + border..][..dim].copy_from_slice(row);
                     }
+                    let mut frame = gif::Frame::from_indexed_pixels(full16, full16, frame_pixels, None);
+                    frame.delay = ATTENTION_DELAY;
+                    encoder.write_frame(&frame)?;
+                }
+                encoder.into_inner()?;
+            }
+            Format::Zpl => write_zpl(code, options, None, out)?,
+        }
+        Ok(())
+    }
+}
+
+/// Writes a ZPL label with the code as a graphic field, centered across `label_width` dots.
+///
+/// Without a label width the label is exactly as wide as the code. The label is always as long as the code.
+fn write_zpl(code: &QrMatrix, options: &RenderOptions, label_width: Option<u32>, out: &mut dyn Write) -> Result<(), Box<dyn std::error::Error>> {
+    let (dim, bitmap) = bitmap(code.modules(), code.width(), options)?;
+    let label_width = label_width.map_or(dim, |width| width as usize);
+    let Some(left) = label_width.checked_sub(dim) else {
+        return Err(format!("Code is {} dots wide and does not fit a {}-dot label.", dim, label_width).into());
+    };
+    let row_bytes = dim.div_ceil(8);
+    let hex: String = bitmap.iter().map(|byte| format!("{:02X}", byte)).collect();
+    writeln!(out, "^XA")?;
+    writeln!(out, "^PW{}", label_width)?;
+    writeln!(out, "^LL{}", dim)?;
+    writeln!(out, "^FO{},0^GFA,{1},{1},{2},{3}^FS", left / 2, bitmap.len(), row_bytes, hex)?;
+    writeln!(out, "^XZ")?;
+    Ok(())
+}
+
+/// Writes the code in the given format, pixelated beyond recognition if `redacted`.
+///
+/// `label_width` is the width in dots of the label `zpl` centers the code on.
+fn write_code(format: Format, code: &QrMatrix, options: &RenderOptions, redacted: bool, label_width: Option<u32>, out: &mut dyn Write) -> Result<(), Box<dyn std::error::Error>> {
+    if redacted {
+        let mut pixelated = code.clone();
+        pixelate(pixelated.modules_mut(), code.width());
+        return write_code(format, &pixelated, options, false, label_width, out);
+    }
+    match format {
+        Format::Zpl => write_zpl(code, options, label_width, out),
+        _ => format.render(code, options, out),
+    }
+}
+
+/// Writes the code to `path`, or to stdout when there is none, and returns the SHA-256 of the bytes written.
+fn write_artifact(format: Format, code: &QrMatrix, options: &RenderOptions, redacted: bool, label_width: Option<u32>, path: Option<&Path>) -> Result<String, Box<dyn std::error::Error>> {
+    let sha256 = match path {
+        Some(path) => {
+            let mut out = HashWriter::new(BufWriter::new(File::create(path)?));
+            write_code(format, code, options, redacted, label_width, &mut out)?;
+            out.finish()?
+        }
+        None => {
+            let mut out = HashWriter::new(BufWriter::new(io::stdout().lock()));
+            write_code(format, code, options, redacted, label_width, &mut out)?;
+            out.finish()?
+        }
+    };
+    Ok(sha256)
+}
+
+/// Largest edge length in pixels of a raster image, which keeps its buffers well under a gigabyte.
+const MAX_IMAGE_DIM: usize = 16384;
+
+/// Rasterizes the code to one palette index per pixel: 0 for light and 1 for dark, see [`palette`].
+///
+/// Returns the edge length in pixels and the pixels in row-major order.
+fn indexed(modules: &[bool], width: usize, options: &RenderOptions) -> Result<(usize, Vec<u8>), Box<dyn std::error::Error>> {
+    let margin = options.margin as usize;
+    let scale = options.scale as usize;
+    let dim = margin.checked_mul(2).and_then(|m| m.checked_add(width)).and_then(|w| w.checked_mul(scale))
+        .filter(|&dim| dim <= MAX_IMAGE_DIM)
+        .ok_or_else(|| format!(
+            "Code with --scale {} and --margin {} is larger than the maximum image size of {} pixels per side.",
+            options.scale, options.margin, MAX_IMAGE_DIM
+        ))?;
+    let outline = options.outline_width() as usize;
+    let mut pixels = vec![0u8; dim * dim];
+    for py in 0..dim {
+        let Some(y) = (py / scale).checked_sub(margin).filter(|&y| y < width) else { continue };
+        for px in 0..dim {
+            let Some(x) = (px / scale).checked_sub(margin).filter(|&x| x < width) else { continue };
+            let (ox, oy) = (px % scale, py % scale);
+            let on_outline = ox < outline || oy < outline || ox >= scale - outline || oy >= scale - outline;
+            if modules[y * width + x] && (!options.outline || on_outline) {
+                pixels[py * dim + px] = 1;
+            }
+        }
+    }
+    Ok((dim, pixels))
+}
+
+/// Width of the `gif-animated` border in modules.
+const ATTENTION_BORDER: usize = 2;
+/// Frames per pulse of the `gif-animated` border.
+const ATTENTION_FRAMES: usize = 12;
+/// Delay between `gif-animated` frames in units of 10 ms.
+const ATTENTION_DELAY: u16 = 10;
+/// How far the border moves from the light toward the dark color at the peak of a pulse.
+const ATTENTION_STRENGTH: f64 = 0.6;
+
+/// Mixes `t` of `to` into `from`.
+fn blend(from: Rgb, to: Rgb, t: f64) -> Rgb {
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// Rasterizes the code to an RGB image.
+fn raster(modules: &[bool], width: usize, options: &RenderOptions) -> Result<ImageBuffer<Pixel<u8>, Vec<u8>>, Box<dyn std::error::Error>> {
+    let (dim, pixels) = indexed(modules, width, options)?;
+    let (Rgb(lr, lg, lb), Rgb(dr, dg, db)) = (options.colors.light, options.colors.dark);
+    Ok(ImageBuffer::from_fn(dim as u32, dim as u32, |x, y| match pixels[y as usize * dim + x as usize] {
+        1 => Pixel([dr, dg, db]),
+        _ => Pixel([lr, lg, lb]),
+    }))
+}
+
+/// The two-color palette for [`indexed`] pixels as `[r, g, b, ...]`.
+fn palette(colors: Colors) -> [u8; 6] {
+    let (Rgb(lr, lg, lb), Rgb(dr, dg, db)) = (colors.light, colors.dark);
+    [lr, lg, lb, dr, dg, db]
+}
+
+/// Packs the code into a 1-bit bitmap, most significant bit first and set for dark pixels.
+///
+/// Returns the edge length in pixels and the rows, each padded to a whole byte.
+fn bitmap(modules: &[bool], width: usize, options: &RenderOptions) -> Result<(usize, Vec<u8>), Box<dyn std::error::Error>> {
+    let (dim, pixels) = indexed(modules, width, options)?;
+    let row_bytes = dim.div_ceil(8);
+    let mut bitmap = vec![0u8; row_bytes * dim];
+    for (py, row) in pixels.chunks(dim.max(1)).enumerate() {
+        for (px, &index) in row.iter().enumerate() {
+            if index == 1 {
+                bitmap[py * row_bytes + px / 8] |= 0x80 >> (px % 8);
+            }
+        }
+    }
+    Ok((dim, bitmap))
+}
+
+/// Reads all of `reader` as UTF-8, failing once it holds more than `limit` bytes instead of buffering it all.
+fn read_bounded(reader: impl Read, limit: u64) -> io::Result<String> {
+    let mut text = String::new();
+    reader.take(limit.saturating_add(1)).read_to_string(&mut text)?;
+    if text.len() as u64 > limit {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("input is larger than {} bytes; raise --max-input-bytes to accept it", limit),
+        ));
+    }
+    Ok(text)
+}
+
+/// Derives the 256-bit WPA pre-shared key from a passphrase as IEEE 802.11i specifies, as 64 hex digits.
+fn derive_psk(passphrase: &str, ssid: &[u8]) -> String {
+    let mut psk = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<sha1::Sha1>(passphrase.as_bytes(), ssid, 4096, &mut psk);
+    audit::hex(&psk)
+}
+
+/// Parses hex digits such as `636166e9` into bytes.
+fn parse_hex(hex: &str) -> Result<Vec<u8>, String> {
+    if !hex.len().is_multiple_of(2) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("'{}' is not an even number of hex digits.", hex));
+    }
+    (0..hex.len()).step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| e.to_string()))
+        .collect()
+}
+
+/// Path of a variant of `path`, e.g. `qr-dark.png`.
+fn variant_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_stem().unwrap_or_default().to_owned();
+    name.push("-");
+    name.push(suffix);
+    if let Some(extension) = path.extension() {
+        name.push(".");
+        name.push(extension);
+    }
+    path.with_file_name(name)
+}
+
+/// Path of a sidecar file next to `path`, e.g. `qr.png.sha256`.
+fn sidecar(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+/// A diagnostic with a stable code, raised as an error in strict mode.
+#[derive(Debug)]
+struct Diagnostic {
+    code: &'static str,
+    /// Process exit code when this diagnostic ends the run.
+    exit: u8,
+    message: String,
+}
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+impl std::error::Error for Diagnostic {}
+
+/// Reports notes, warnings, and errors on stderr.
+///
+/// In porcelain mode every line has the form `qrfi:<level>:<code>:<message>` so scripts can parse it.
+struct Diagnostics {
+    strict: bool,
+    porcelain: bool,
+}
+impl Diagnostics {
+    fn print(&self, level: &str, code: &str, message: &str) {
+        if self.porcelain {
+            eprintln!("qrfi:{}:{}:{}", level, code, message.replace('\n', " "));
+        } else {
+            eprintln!("{}: {}", level, message);
+        }
+    }
+
+    /// Prints an informational note.
+    fn note(&self, code: &'static str, message: String) {
+        self.print("note", code, &message);
+    }
+
+    /// Prints a warning, or fails with it in strict mode.
+    fn warn(&self, code: &'static str, message: String) -> Result<(), Box<dyn std::error::Error>> {
+        if self.strict {
+            return Err(Diagnostic { code, exit: 1, message }.into());
+        }
+        self.print("warning", code, &message);
+        Ok(())
+    }
+
+    /// Prints the error that ended the run.
+    fn error(&self, error: &(dyn std::error::Error + 'static)) {
+        if self.porcelain {
+            let code = error.downcast_ref::<Diagnostic>().map_or("general", |d| d.code);
+            self.print("error", code, &error.to_string());
+        } else {
+            eprintln!("Error: {}", error);
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let command = Args::command();
+    let matches = command.clone().get_matches();
+    let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if let Some(shell) = args.prompt_snippet {
+        print!("{}", snippet::render(shell, &command, &matches));
+        return ExitCode::SUCCESS;
+    }
+    let diagnostics = Diagnostics { strict: args.strict, porcelain: args.porcelain };
+    match run(args, &diagnostics) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            diagnostics.error(&*error);
+            ExitCode::from(error.downcast_ref::<Diagnostic>().map_or(1, |d| d.exit))
+        }
+    }
+}
+
+fn run(mut args: Args, diagnostics: &Diagnostics) -> Result<(), Box<dyn std::error::Error>> {
+    if args.interactive {
+        wizard::run(&mut args)?;
+    } else if args.ssid.is_none() && args.ssid_hex.is_none() && !io::stdin().is_terminal() {
+        let buffer = read_bounded(io::stdin(), args.max_input_bytes)
+            .map_err(|e| format!("Cannot read the SSID from stdin: {}", e))?;
+        args.ssid = Some(buffer.trim_end_matches(['\n', '\r']).to_string());
+    }
+    let ssid_bytes = match &args.ssid_hex {
+        Some(hex) => parse_hex(hex)?,
+        None => args.ssid.clone().unwrap_or_default().into_bytes(),
+    };
+    // Report every invalid field at once rather than one per run.
+    let mut errors = Wifi::validate_all_with_policy(&ssid_bytes, args.password.as_deref(), args.authentication_type, args.validation);
+    if let Some(last) = errors.pop() {
+        for error in &errors {
+            diagnostics.error(error);
+        }
+        return Err(last.into());
+    }
+    for error in Wifi::validate_all(&ssid_bytes, args.password.as_deref(), args.authentication_type) {
+        let policy = match args.validation {
+            ValidationPolicy::Strict => "strict",
+            ValidationPolicy::Lenient => "lenient",
+            ValidationPolicy::Off => "off",
+        };
+        diagnostics.note("validation", format!("Generating anyway with --validation {}: {}", policy, error));
+    }
+    let ssid = Ssid::from_bytes_with_policy(&ssid_bytes, args.validation)?;
+    if args.ssid_hex.is_some() {
+        // Policies, audit logs, and text files see a non-UTF-8 SSID with replacement characters.
+        args.ssid = Some(String::from_utf8_lossy(ssid.as_bytes()).into_owned());
+    }
+    if ssid.as_str().is_none() && !args.export.is_empty() {
+        return Err("--export requires an SSID that is valid UTF-8.".into());
+    }
+    let password = match args.password.as_deref() {
+        Some(passphrase) if args.emit_psk && args.authentication_type == AuthType::Wpa && passphrase.len() < 64 => {
+            Password::new(Some(derive_psk(passphrase, ssid.as_bytes())), AuthType::Wpa)?
+        }
+        _ if args.emit_psk => return Err("--emit-psk requires a WPA passphrase.".into()),
+        _ => Password::new_with_policy(args.password.clone(), args.authentication_type, args.validation)?,
+    };
+    if args.transition_disable && !matches!(args.authentication_type, AuthType::Wpa | AuthType::Sae) {
+        return Err("--transition-disable requires --authentication-type WPA or SAE.".into());
+    }
+    let mut wifi = Wifi::new(ssid, password, args.hidden)
+        .with_hex_ssid(args.hex_ssid)
+        .with_transition_disable(args.transition_disable)
+        .with_sae_as_wpa(args.sae_as_wpa)
+        .with_compat_profile(args.compat);
+    match args.eap {
+        Some(method) => {
+            let mut enterprise = Enterprise::new(method)
+                .with_identity(args.identity.clone().unwrap_or_default())
+                .with_anonymous_identity(args.anonymous_identity.clone().unwrap_or_default());
+            if let Some(phase2) = args.phase2 {
+                enterprise = enterprise.with_phase2(phase2);
+            }
+            wifi = wifi.with_enterprise(enterprise).map_err(|_| "--eap requires --authentication-type WPA2-EAP.")?;
+        }
+        None if args.authentication_type == AuthType::Wpa2Eap => {
+            return Err("--authentication-type WPA2-EAP requires --eap.".into());
+        }
+        None => {}
+    }
+    if args.dialect == Dialect::WifiUri {
+        if args.hex_ssid {
+            return Err("--hex-ssid requires --dialect mecard; the WPA3 URI percent-encodes SSIDs.".into());
+        }
+        if args.authentication_type == AuthType::Wpa2Eap {
+            return Err("--dialect wifi-uri cannot describe WPA2-EAP networks.".into());
+        }
+        if args.compat != CompatProfile::Zxing {
+            return Err("--compat requires --dialect mecard; the WPA3 URI has a fixed layout.".into());
+        }
+        if args.explain || args.compat_report {
+            return Err("--explain and --compat-report require --dialect mecard.".into());
+        }
+    }
+    // Lints point out likely mistakes but never stop the run, not even in strict mode.
+    for lint in wifi.lints() {
+        diagnostics.print("warning", lint.code(), &lint.to_string());
+    }
+    if args.tape.is_some() && args.format != Format::Png {
+        return Err("--tape requires --format png.".into());
+    }
+    if args.label_width.is_some() && args.format != Format::Zpl {
+        return Err("--label-width requires --format zpl.".into());
+    }
+    if args.watermark.is_some() && !matches!(args.format, Format::Svg | Format::Svgz) {
+        return Err("--watermark requires --format svg or svgz.".into());
+    }
+    if args.watermark.is_some() && args.bundle.is_some() {
+        return Err("--watermark cannot be used with --bundle, whose PNG could not show it.".into());
+    }
+    if args.label.is_some() && !matches!(args.format, Format::Svg | Format::Svgz) {
+        return Err("--label requires --format svg or svgz.".into());
+    }
+    if args.outline && args.format == Format::Ascii {
+        return Err("--outline is not supported for --format ascii.".into());
+    }
+    let policy = args.policy.as_deref().map(|path| policy::Policy::load(path, args.max_input_bytes)).transpose()?;
+    if let Some(policy) = &policy {
+        // Rates the passphrase even with --emit-psk; the derived key is only as hard to guess as the passphrase.
+        let password = match args.authentication_type {
+            AuthType::Nopass => None,
+            _ => args.password.as_deref(),
+        };
+        policy.check_network(args.ssid.as_deref().unwrap_or_default(), password)?;
+    }
+    if args.explain {
+        for line in explain::lines(&wifi, args.hex_ssid, args.sae_as_wpa, args.compat) {
+            diagnostics.note("explain", line);
+        }
+    }
+    if args.compat_report {
+        let caveats = compat::caveats(&wifi, args.hex_ssid, args.sae_as_wpa);
+        if caveats.is_empty() {
+            println!("No known reader caveats.");
+        }
+        for rule in caveats {
+            println!("- {}: {}", rule.readers, rule.caveat);
+        }
+        return Ok(());
+    }
+    let mut options = RenderOptions {
+        scale: args.scale,
+        margin: args.margin,
+        ecl: args.error_correction,
+        colors: Colors { dark: args.dark_color, light: args.light_color },
+        watermark: args.watermark.clone(),
+        outline: args.outline,
+        label: args.label.clone(),
+    };
+    let code = match args.dialect {
+        Dialect::Mecard => wifi.to_qr(options.ecl)?,
+        Dialect::WifiUri => QrMatrix::encode(wifi.to_uri().as_bytes(), options.ecl)?,
+    };
+    if let Some(preset) = args.preset {
+        let (target_px, dpi, margin) = preset.sizing();
+        options.margin = margin;
+        options.scale = (target_px / (code.width() as u32 + margin * 2)).max(1);
+        args.dpi = dpi;
+    }
+    if let Some(tape) = args.tape {
+        let total_width = options.margin.saturating_mul(2).saturating_add(code.width() as u32);
+        options.scale = tape.printable_dots() / total_width;
+        if options.scale == 0 {
+            return Err(format!(
+                "Code is {} modules wide and does not fit the {} printable dots of the tape.",
+                total_width, tape.printable_dots()
+            ).into());
+        }
+        options.colors = Colors::default();
+        args.dpi = 180;
+    }
+    // Zebra printers magnify the graphic field by whole dots only, so the largest module size that fits wins.
+    let label_width = args.label_width.map(|mm| (mm / 25.4 * args.dpi as f64).floor() as u32);
+    if let Some(dots) = label_width {
+        let total_width = options.margin.saturating_mul(2).saturating_add(code.width() as u32);
+        options.scale = dots / total_width;
+        if options.scale == 0 {
+            return Err(format!(
+                "Code is {} modules wide and does not fit the {} dots of the label at {} dpi.",
+                total_width, dots, args.dpi
+            ).into());
+        }
+    }
+    // The label takes its band from the bottom margin, so that side has the smallest quiet zone.
+    let quiet_zone = match options.label {
+        Some(_) => options.margin.saturating_sub(RenderOptions::LABEL_HEIGHT),
+        None => options.margin,
+    };
+    if options.label.is_some() && quiet_zone < scan::MIN_QUIET_ZONE {
+        return Err(format!(
+            "--label needs a margin of at least {} modules to keep a {}-module quiet zone below the code.",
+            scan::MIN_QUIET_ZONE + RenderOptions::LABEL_HEIGHT, scan::MIN_QUIET_ZONE
+        ).into());
+    }
+    if let Some(policy) = &policy {
+        policy.check_output(&options)?;
+    }
+    if options.outline {
+        diagnostics.warn("outline", "Outlined modules save ink but scan less reliably; test a print before handing it out.".to_string())?;
+    }
+    if i16::from(code.version()) > args.max_version {
+        let suggestion = if options.ecl == ErrorCorrection::L {
+            "Shorten the SSID or passphrase."
+        } else {
+            "Shorten the SSID or passphrase, or lower the error correction level."
+        };
+        let message = format!(
+            "Payload needs QR version {} which exceeds the maximum of {}; dense codes scan poorly from a distance. {}",
+            code.version(), args.max_version, suggestion
+        );
+        diagnostics.warn("max-version", message)?;
+    }
+    if let Some(distance) = args.scan_distance {
+        let total_width = options.margin.saturating_mul(2).saturating_add(code.width() as u32);
+        let min_module = scan::min_module_mm(distance, code.width() as u32);
+        let max_dpi = (options.scale as f64 * 25.4 / min_module).floor();
+        let printed_mm = min_module * total_width as f64;
+        if max_dpi < 1.0 {
+            // Not even 1 dpi makes the modules large enough, so only larger modules or a closer scanner help.
+            let min_scale = (min_module * args.dpi as f64 / 25.4).ceil();
+            diagnostics.note("scan-distance", format!(
+                "Scanning from {} mm needs a printed size of at least {:.0} mm, which {} px modules do not reach at any resolution.",
+                distance, printed_mm, options.scale
+            ));
+            diagnostics.warn("scan-distance", format!(
+                "Output at {} dpi is too small to scan from {} mm; raise --scale to {} or more, or scan from closer.",
+                args.dpi, distance, min_scale
+            ))?;
+        } else {
+            diagnostics.note("scan-distance", format!(
+                "Scanning from {} mm needs a printed size of at least {:.0} mm, i.e. at most {} dpi with {} px modules.",
+                distance, printed_mm, max_dpi, options.scale
+            ));
+            if scan::px_to_mm(options.scale, args.dpi) < min_module {
+                diagnostics.warn("scan-distance", format!(
+                    "Output at {} dpi is too small to scan from {} mm; lower --dpi to {} or less.",
+                    args.dpi, distance, max_dpi
+                ))?;
+            }
+        }
+    }
+    let ssid = args.ssid.as_deref().unwrap_or_default();
+    // Read back from the payload so that with --emit-psk scripts and cards get the derived key, not the passphrase.
+    let password = match args.authentication_type {
+        AuthType::Nopass => None,
+        _ => wifi.password().expose_secret(),
+    };
+    let network = export::Network { ssid, auth_type: args.authentication_type, password, hidden: args.hidden };
+    for export in &args.export {
+        export.validate(&network)?;
+    }
+    // Everything below prints a report or writes output, so a dry run stops here with every check behind it.
+    if args.check {
+        return Ok(());
+    }
+    if args.score {
+        let profile = scan::ScanProfile {
+            module_mm: scan::px_to_mm(options.scale, args.dpi),
+            colors: options.colors,
+            ecl: options.ecl,
+        };
+        let (score, suggestions) = scan::score(&profile);
+        println!("Scanability score: {}/100", score);
+        for suggestion in suggestions {
+            println!("- {}", suggestion);
+        }
+        return Ok(());
+    }
+    if args.compliance_report {
+        let checks = scan::compliance(
+            quiet_zone,
+            code.width() as u32,
+            scan::px_to_mm(options.scale, args.dpi),
+            args.scan_distance.unwrap_or(200.0),
+        );
+        for check in &checks {
+            println!("{}: {} ... {}", check.name, check.detail, if check.passed { "PASS" } else { "FAIL" });
+        }
+        if checks.iter().any(|check| !check.passed) {
+            return Err(Diagnostic { code: "compliance", exit: 1, message: "Output does not meet QR print guidelines.".to_string() }.into());
+        }
+        println!("Result: PASS");
+        return Ok(());
+    }
+    // Codes, scripts, and cards of a secured network are a fixed function of the SSID and password, so anyone
+    // with the log could test password guesses against their hashes. Only `secret`-free artifacts are hashed.
+    let audit = |artifact: &Path, sha256: &str, secret: bool| -> io::Result<()> {
+        match &args.audit_log {
+            Some(log) => audit::append(log, ssid, &artifact.to_string_lossy(), (!secret).then_some(sha256)),
+            None => Ok(()),
+        }
+    };
+    if !args.export.is_empty() {
+        fs::create_dir_all(&args.export_dir)?;
+        for export in &args.export {
+            for (name, contents) in export.files(&network) {
+                let path = args.export_dir.join(name);
+                fs::write(&path, &contents)?;
+                #[cfg(unix)]
+                if name.ends_with(".sh") {
+                    use std::os::unix::fs::PermissionsExt;
+                    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+                }
+                audit(&path, &audit::hex(&Sha256::digest(&contents)), password.is_some())?;
+            }
+        }
+    }
+    if let Some(dir) = &args.bundle {
+        fs::create_dir_all(dir)?;
+        for (name, format) in [("qr.png", Format::Png), ("qr.svg", Format::Svg)] {
+            let path = dir.join(name);
+            let sha256 = write_artifact(format, &code, &options, args.redacted, None, Some(&path))?;
+            audit(&path, &sha256, password.is_some())?;
+        }
+        // A fixed-length mask so the proof does not reveal the password length either.
+        let shown_password = match password {
+            Some(_) if args.redacted => "********",
+            Some(password) => password,
+            None => "(none)",
+        };
+        let texts = [
+            ("alt.txt", format!("QR code to join the Wi-Fi network \"{}\".\n", ssid)),
+            ("credentials.txt", format!(
+                "Network:  {}\nPassword: {}\nSecurity: {}\nHidden:   {}\n",
+                ssid, shown_password, args.authentication_type, if args.hidden { "yes" } else { "no" }
+            )),
+        ];
+        for (name, text) in texts {
+            let path = dir.join(name);
+            fs::write(&path, &text)?;
+            let secret = name == "credentials.txt" && password.is_some() && !args.redacted;
+            audit(&path, &audit::hex(&Sha256::digest(&text)), secret)?;
+        }
+        return Ok(());
+    }
+    let targets = match &args.output {
+        Some(path) if !args.variants.is_empty() => args.variants.iter()
+            .map(|variant| {
+                let options = RenderOptions { colors: variant.colors(options.colors), ..options.clone() };
+                (Some(variant_path(path, variant.suffix())), options)
+            })
+            .collect(),
+        output => vec![(output.clone(), options)],
+    };
+    for (output, options) in &targets {
+        let sha256 = write_artifact(args.format, &code, options, args.redacted, label_width, output.as_deref())?;
+        audit(output.as_deref().unwrap_or(Path::new("-")), &sha256, password.is_some())?;
+        if let Some(path) = output {
+            if let Some(Checksum::Sha256) = args.checksum {
+                let name = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy();
+                fs::write(sidecar(path, "sha256"), format!("{}  {}\n", sha256, name))?;
+            }
+            if let Some(key) = &args.minisign_key {
+                let status = Command::new("minisign")
+                    .arg("-S").arg("-s").arg(key).arg("-m").arg(path)
+                    .status()
+                    .map_err(|e| format!("Failed to run minisign: {}", e))?;
+                if !status.success() {
+                    return Err(format!("minisign exited with {}.", status).into());
                 }
             }
-            let mut buf = Cursor::new(Vec::new());
-            img.write_to(&mut buf, ImageFormat::Png)?;
-            io::stdout().write_all(buf.get_ref())?;
-        }
-        Format::Svg => {
-            let svg_image = code.render()
-                .min_dimensions(200, 200)
-                .dark_color(qrcode::render::svg::Color("#000000"))
-                .light_color(qrcode::render::svg::Color("#ffffff"))
-                .build();
-            println!("{}", svg_image);
         }
     }
     Ok(())
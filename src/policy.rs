@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+use qrfi::{ErrorCorrection, RenderOptions};
+use serde::Deserialize;
+
+use crate::Diagnostic;
+
+/// Organization policy loaded from a TOML file.
+///
+/// ```toml
+/// [ssid]
+/// deny = ["corp-prod"]
+/// allow = ["guest", "lobby"]
+///
+/// [password]
+/// min_entropy_bits = 60
+///
+/// [output]
+/// min_error_correction = "Q"
+/// min_scale = 8
+/// ```
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Policy {
+    ssid: SsidRules,
+    password: PasswordRules,
+    output: OutputRules,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+struct SsidRules {
+    /// SSIDs that must never be encoded.
+    deny: Vec<String>,
+    /// If present, the only SSIDs that may be encoded.
+    allow: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+struct PasswordRules {
+    min_entropy_bits: Option<f64>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+struct OutputRules {
+    min_error_correction: Option<String>,
+    min_scale: Option<u32>,
+}
+
+/// Exit code for an SSID the policy forbids.
+pub const EXIT_SSID: u8 = 10;
+/// Exit code for a password below the required entropy.
+pub const EXIT_PASSWORD: u8 = 11;
+/// Exit code for output below the required error correction or size.
+pub const EXIT_OUTPUT: u8 = 12;
+
+/// Estimates password entropy in bits from its length and the character classes it uses.
+pub fn entropy_bits(password: &str) -> f64 {
+    let mut pool = 0;
+    if password.chars().any(|c| c.is_ascii_lowercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_uppercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_digit()) {
+        pool += 10;
+    }
+    if password.chars().any(|c| c.is_ascii_punctuation() || c == ' ') {
+        pool += 33;
+    }
+    if !password.is_ascii() {
+        pool += 100;
+    }
+    if pool == 0 {
+        return 0.0;
+    }
+    password.chars().count() as f64 * (pool as f64).log2()
+}
+
+fn violation(code: &'static str, exit: u8, message: String) -> Diagnostic {
+    Diagnostic { code, exit, message }
+}
+
+impl Policy {
+    /// Reads and parses a policy file of at most `max_bytes`.
+    pub fn load(path: &Path, max_bytes: u64) -> Result<Self, String> {
+        let text = std::fs::File::open(path)
+            .and_then(|file| crate::read_bounded(file, max_bytes))
+            .map_err(|e| format!("Cannot read policy file {}: {}", path.display(), e))?;
+        let policy: Policy = toml::from_str(&text)
+            .map_err(|e| format!("Invalid policy file {}: {}", path.display(), e))?;
+        if let Some(level) = &policy.output.min_error_correction {
+            ErrorCorrection::from_str(level, false)
+                .map_err(|e| format!("Invalid policy file {}: min_error_correction: {}", path.display(), e))?;
+        }
+        Ok(policy)
+    }
+
+    /// Checks the SSID and password, returning the first violation.
+    pub fn check_network(&self, ssid: &str, password: Option<&str>) -> Result<(), Diagnostic> {
+        if self.ssid.deny.iter().any(|denied| denied == ssid) {
+            return Err(violation("policy-ssid", EXIT_SSID, format!("Policy forbids encoding the SSID \"{}\".", ssid)));
+        }
+        if let Some(allow) = &self.ssid.allow && !allow.iter().any(|allowed| allowed == ssid) {
+            return Err(violation("policy-ssid", EXIT_SSID, format!("Policy does not allow encoding the SSID \"{}\".", ssid)));
+        }
+        if let (Some(min), Some(password)) = (self.password.min_entropy_bits, password) {
+            let bits = entropy_bits(password);
+            if bits < min {
+                return Err(violation("policy-password", EXIT_PASSWORD, format!(
+                    "Password has about {:.0} bits of entropy; policy requires at least {}.", bits, min
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the render options, returning the first violation.
+    pub fn check_output(&self, options: &RenderOptions) -> Result<(), Diagnostic> {
+        if let Some(level) = &self.output.min_error_correction
+            && let Ok(min) = ErrorCorrection::from_str(level, false)
+            && options.ecl < min
+        {
+            return Err(violation("policy-output", EXIT_OUTPUT, format!(
+                "Policy requires error correction level {} or higher.", level
+            )));
+        }
+        if let Some(min) = self.output.min_scale && options.scale < min {
+            return Err(violation("policy-output", EXIT_OUTPUT, format!(
+                "Policy requires at least {} pixels per module; got {}.", min, options.scale
+            )));
+        }
+        Ok(())
+    }
+}
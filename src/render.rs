@@ -0,0 +1,396 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use crate::EncodeError;
+
+/// QR error correction level.
+///
+/// Higher levels survive more damage at the cost of a denser code.
+///
+/// # Example
+///
+/// ```
+/// use qrfi::ErrorCorrection;
+///
+/// assert_eq!(ErrorCorrection::default(), ErrorCorrection::M);
+/// assert!(ErrorCorrection::L < ErrorCorrection::H);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum ErrorCorrection {
+    /// Recovers about 7% of the code.
+    #[cfg_attr(feature = "cli", value(name = "L"))]
+    L,
+    /// Recovers about 15% of the code.
+    #[default]
+    #[cfg_attr(feature = "cli", value(name = "M"))]
+    M,
+    /// Recovers about 25% of the code.
+    #[cfg_attr(feature = "cli", value(name = "Q"))]
+    Q,
+    /// Recovers about 30% of the code.
+    #[cfg_attr(feature = "cli", value(name = "H"))]
+    H,
+}
+#[cfg(feature = "std")]
+impl From<ErrorCorrection> for qrcode::EcLevel {
+    fn from(ecl: ErrorCorrection) -> Self {
+        match ecl {
+            ErrorCorrection::L => qrcode::EcLevel::L,
+            ErrorCorrection::M => qrcode::EcLevel::M,
+            ErrorCorrection::Q => qrcode::EcLevel::Q,
+            ErrorCorrection::H => qrcode::EcLevel::H,
+        }
+    }
+}
+
+/// The modules of an encoded QR code, ready to draw with any renderer.
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{ErrorCorrection, WifiBuilder};
+///
+/// let qr = WifiBuilder::new("SSID").wpa("PASSWORD").build().unwrap().to_qr(ErrorCorrection::M).unwrap();
+/// assert_eq!(qr.width(), 17 + 4 * qr.version() as usize);
+/// assert_eq!(qr.modules().len(), qr.width() * qr.width());
+/// // The top-left finder pattern starts with a dark module.
+/// assert!(qr.is_dark(0, 0));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct QrMatrix {
+    width: usize,
+    version: u8,
+    modules: Vec<bool>,
+}
+#[cfg(feature = "std")]
+impl QrMatrix {
+    /// Encodes `data` in byte mode with the smallest version that fits, for payloads other than
+    /// [`Wifi::to_mecard`](crate::Wifi::to_mecard) such as [`Wifi::to_uri`](crate::Wifi::to_uri).
+    pub fn encode(data: &[u8], ecl: ErrorCorrection) -> Result<Self, EncodeError> {
+        // Byte-mode data can only fail to encode by not fitting into version 40.
+        let code = qrcode::QrCode::with_error_correction_level(data, ecl.into()).map_err(|_| EncodeError::DataTooLong)?;
+        let version = match code.version() {
+            qrcode::Version::Normal(version) | qrcode::Version::Micro(version) => version as u8,
+        };
+        let modules = code.to_colors().into_iter().map(|color| color == qrcode::Color::Dark).collect();
+        Ok(Self { width: code.width(), version, modules })
+    }
+    /// Modules per side, without a quiet zone.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    /// QR version from 1 to 40; the width is `17 + 4 * version`.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+    /// `width * width` entries in row-major order, `true` for dark modules, as [`write_svg`] takes them.
+    pub fn modules(&self) -> &[bool] {
+        &self.modules
+    }
+    /// The modules, for post-processing such as pixelating a layout proof before rendering.
+    pub fn modules_mut(&mut self) -> &mut [bool] {
+        &mut self.modules
+    }
+    /// Every module as `(x, y, dark)`, row by row from the top left, for renderers that plot one module at a time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use qrfi::{ErrorCorrection, WifiBuilder};
+    ///
+    /// let qr = WifiBuilder::new("SSID").build().unwrap().to_qr(ErrorCorrection::M).unwrap();
+    /// let dark = qr.iter().filter(|&(_, _, dark)| dark).count();
+    /// assert_eq!(dark, qr.modules().iter().filter(|&&dark| dark).count());
+    /// assert_eq!(qr.iter().last(), Some((qr.width() - 1, qr.width() - 1, qr.is_dark(qr.width() - 1, qr.width() - 1))));
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, bool)> + '_ {
+        let width = self.width;
+        // The modules are empty if the width is 0, so the closure never divides by it.
+        self.modules.iter().enumerate().map(move |(i, &dark)| (i % width, i / width, dark))
+    }
+    pub(crate) fn into_modules(self) -> Vec<bool> {
+        self.modules
+    }
+    /// Whether the module in column `x` of row `y` is dark. Modules outside the code are light, like the quiet zone.
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.width && self.modules.get(y * self.width + x) == Some(&true)
+    }
+}
+
+/// An sRGB color, parsed from and displayed as `#rrggbb`.
+///
+/// # Example
+///
+/// ```
+/// use qrfi::Rgb;
+///
+/// let color: Rgb = "#1e90ff".parse().unwrap();
+/// assert_eq!(color, Rgb(0x1e, 0x90, 0xff));
+/// assert_eq!(color.to_string(), "#1e90ff");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+}
+impl core::str::FromStr for Rgb {
+    type Err = String;
+
+    /// Parses `#rrggbb` or `rrggbb`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("'{}' is not a color in #rrggbb form.", s));
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| e.to_string());
+        Ok(Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+impl core::fmt::Display for Rgb {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Colors of the dark and light modules.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Colors {
+    pub dark: Rgb,
+    pub light: Rgb,
+}
+impl Default for Colors {
+    fn default() -> Self {
+        Self { dark: Rgb::BLACK, light: Rgb::WHITE }
+    }
+}
+
+/// Options consumed by every output format.
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{ErrorCorrection, RenderOptions};
+///
+/// let options = RenderOptions { ecl: ErrorCorrection::H, ..Default::default() };
+/// assert_eq!(options.scale, 10);
+/// assert_eq!(options.margin, 4);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RenderOptions {
+    /// Pixels per module in raster and vector output.
+    pub scale: u32,
+    /// Quiet zone around the code in modules.
+    pub margin: u32,
+    /// Error correction level used to encode the payload.
+    pub ecl: ErrorCorrection,
+    /// Colors of the dark and light modules.
+    pub colors: Colors,
+    /// Faint diagonal text drawn behind the modules, for drafts. Only [`write_svg`] and [`SvgRenderer`] draw it.
+    pub watermark: Option<String>,
+    /// Draw dark modules as outlined squares to save ink on draft prints. Outlined codes scan less reliably.
+    pub outline: bool,
+    /// Text centered in the outermost [`RenderOptions::LABEL_HEIGHT`] modules of the bottom margin.
+    pub label: Option<String>,
+}
+impl RenderOptions {
+    /// Height of the band in modules that `label` takes from the bottom margin.
+    ///
+    /// The quiet zone left below the code is `margin - LABEL_HEIGHT`.
+    pub const LABEL_HEIGHT: u32 = 2;
+
+    /// Width in pixels of the outline drawn around each dark module when `outline` is set.
+    ///
+    /// Modules smaller than three pixels are filled, since a thinner outline would not print.
+    pub fn outline_width(&self) -> u32 {
+        if self.scale < 3 { self.scale } else { (self.scale / 4).max(1) }
+    }
+}
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            scale: 10,
+            margin: 4,
+            ecl: ErrorCorrection::default(),
+            colors: Colors::default(),
+            watermark: None,
+            outline: false,
+            label: None,
+        }
+    }
+}
+
+/// Escapes text for use in XML character data.
+#[cfg(feature = "std")]
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Writes the modules as an SVG document, one row at a time, without building the document in memory.
+///
+/// `modules` holds `width * width` entries in row-major order, `true` for dark modules. Any other length, a
+/// width of zero, or a size that does not fit in `usize` is an [`std::io::ErrorKind::InvalidInput`] error.
+///
+/// # Example
+///
+/// ```
+/// use qrfi::{write_svg, RenderOptions};
+///
+/// let mut out = Vec::new();
+/// write_svg(&[true, false, false, true], 2, &RenderOptions::default(), &mut out).unwrap();
+/// let svg = String::from_utf8(out).unwrap();
+/// assert!(svg.contains(r#"d="M40 40h10v10h-10z M50 50h10v10h-10z""#));
+/// ```
+#[cfg(feature = "std")]
+pub fn write_svg<W: std::io::Write>(modules: &[bool], width: usize, options: &RenderOptions, mut out: W) -> std::io::Result<()> {
+    let scale = options.scale as usize;
+    let margin = options.margin as usize;
+    if width == 0 || width.checked_mul(width) != Some(modules.len()) {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "modules do not form a square of the given width"));
+    }
+    // Every coordinate below is at most `dim`, so checking it once rules out overflow everywhere.
+    let dim = margin.checked_mul(2).and_then(|m| m.checked_add(width)).and_then(|w| w.checked_mul(scale))
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "code is too large for an SVG document"))?;
+    write!(
+        out,
+        concat!(
+            r#"<?xml version="1.0" standalone="yes"?>"#,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="{0}" height="{0}" viewBox="0 0 {0} {0}" shape-rendering="crispEdges">"#,
+            r#"<rect x="0" y="0" width="{0}" height="{0}" fill="{1}"/>"#,
+        ),
+        dim, options.colors.light
+    )?;
+    if let Some(text) = &options.watermark {
+        // Fit the text along the diagonal. It is drawn before the modules at low opacity, so dark modules stay
+        // untouched and light modules only lose a little contrast.
+        let center = dim as f64 / 2.0;
+        let size = (dim as f64 * 1.2 / (text.chars().count().max(1) as f64 * 0.6)).min(dim as f64 / 8.0);
+        write!(
+            out,
+            concat!(
+                r#"<text x="{0}" y="{0}" transform="rotate(-45 {0} {0})" text-anchor="middle" dominant-baseline="middle" "#,
+                r#"font-family="sans-serif" font-weight="bold" font-size="{1:.1}" fill="{2}" fill-opacity="0.15">{3}</text>"#,
+            ),
+            center, size, options.colors.dark, escape_xml(text)
+        )?;
+    }
+    let fill_rule = if options.outline { r#" fill-rule="evenodd""# } else { "" };
+    write!(out, r#"<path fill="{}"{} d=""#, options.colors.dark, fill_rule)?;
+    let outline = options.outline_width() as usize;
+    let mut separator = "";
+    for (y, row) in modules.chunks(width).enumerate() {
+        let mut x = 0;
+        while let Some(&dark) = row.get(x) {
+            if !dark {
+                x += 1;
+                continue;
+            }
+            let run = if options.outline { 1 } else { row.iter().skip(x).take_while(|&&dark| dark).count() };
+            let (px, py) = ((x + margin) * scale, (y + margin) * scale);
+            write!(out, "{}M{} {}h{}v{}h-{}z", separator, px, py, run * scale, scale, run * scale)?;
+            if options.outline && outline * 2 < scale {
+                // Cut out the inside of the module; the even-odd rule leaves only the ring.
+                let inner = scale - outline * 2;
+                write!(out, " M{} {}h{}v{}h-{}z", px + outline, py + outline, inner, inner, inner)?;
+            }
+            separator = " ";
+            x += run;
+        }
+    }
+    write!(out, r#""/>"#)?;
+    if let Some(text) = &options.label {
+        let band = RenderOptions::LABEL_HEIGHT as usize * scale;
+        let font_size = band as f64 * 0.7;
+        // Squeeze long labels to the width of the code rather than letting them run into the side margins.
+        let available = width * scale;
+        let text_length = if text.chars().count() as f64 * font_size * 0.6 > available as f64 {
+            format!(r#" textLength="{}" lengthAdjust="spacingAndGlyphs""#, available)
+        } else {
+            String::new()
+        };
+        write!(
+            out,
+            concat!(
+                r#"<text x="{0}" y="{1}" text-anchor="middle" dominant-baseline="middle" font-family="sans-serif" "#,
+                r#"font-size="{2:.1}" fill="{3}"{4}>{5}</text>"#,
+            ),
+            dim / 2, dim - band / 2, font_size, options.colors.dark, text_length, escape_xml(text)
+        )?;
+    }
+    writeln!(out, "</svg>")
+}
+
+/// An output format that draws a [`QrMatrix`].
+///
+/// The library provides [`AsciiRenderer`] and [`SvgRenderer`]; implement the trait to add formats of your own.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Write;
+/// use qrfi::{ErrorCorrection, QrMatrix, RenderOptions, Renderer, WifiBuilder};
+///
+/// /// One line of `#` and `.` per row, without a quiet zone.
+/// struct Hashes;
+/// impl Renderer for Hashes {
+///     fn render(&self, matrix: &QrMatrix, _: &RenderOptions, out: &mut dyn Write) -> Result<(), Box<dyn std::error::Error>> {
+///         for row in matrix.modules().chunks(matrix.width()) {
+///             let line: String = row.iter().map(|&dark| if dark { '#' } else { '.' }).collect();
+///             writeln!(out, "{}", line)?;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let qr = WifiBuilder::new("SSID").build().unwrap().to_qr(ErrorCorrection::M).unwrap();
+/// let mut out = Vec::new();
+/// Hashes.render(&qr, &RenderOptions::default(), &mut out).unwrap();
+/// assert!(String::from_utf8(out).unwrap().starts_with("#######."));
+/// ```
+#[cfg(feature = "std")]
+pub trait Renderer {
+    /// Writes `matrix` to `out` in this format.
+    fn render(&self, matrix: &QrMatrix, options: &RenderOptions, out: &mut dyn std::io::Write) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// Draws the code with Unicode half blocks, two rows per line, for terminals.
+///
+/// Only `margin` is used from the options; the terminal decides the colors.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AsciiRenderer;
+#[cfg(feature = "std")]
+impl Renderer for AsciiRenderer {
+    fn render(&self, matrix: &QrMatrix, options: &RenderOptions, out: &mut dyn std::io::Write) -> Result<(), Box<dyn std::error::Error>> {
+        use qrcode::render::unicode::Dense1x2;
+        // The terminal canvas counts characters in a u32, which a u16 side length keeps from overflowing.
+        let side = options.margin.checked_mul(2).and_then(|m| m.checked_add(u32::try_from(matrix.width()).ok()?));
+        if side.is_none_or(|side| side > u32::from(u16::MAX)) {
+            return Err("Margin is too large to draw the code in a terminal.".into());
+        }
+        let colors: Vec<qrcode::Color> =
+            matrix.modules().iter().map(|&dark| if dark { qrcode::Color::Dark } else { qrcode::Color::Light }).collect();
+        let image = qrcode::render::Renderer::<Dense1x2>::new(&colors, matrix.width(), options.margin)
+            .dark_color(Dense1x2::Dark)
+            .light_color(Dense1x2::Light)
+            .build();
+        writeln!(out, "{}", image)?;
+        Ok(())
+    }
+}
+
+/// Writes the code as an SVG document, see [`write_svg`].
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SvgRenderer;
+#[cfg(feature = "std")]
+impl Renderer for SvgRenderer {
+    fn render(&self, matrix: &QrMatrix, options: &RenderOptions, out: &mut dyn std::io::Write) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(write_svg(matrix.modules(), matrix.width(), options, out)?)
+    }
+}
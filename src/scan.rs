@@ -0,0 +1,125 @@
+use qrfi::{Colors, ErrorCorrection, Rgb};
+
+/// Physical and visual properties of a rendered code that affect how easily it scans.
+pub struct ScanProfile {
+    /// Edge length of a single module in millimetres.
+    pub module_mm: f64,
+    /// Colors of the dark and light modules.
+    pub colors: Colors,
+    /// Error correction level of the code.
+    pub ecl: ErrorCorrection,
+}
+
+/// Converts a length in pixels to millimetres at the given resolution.
+pub fn px_to_mm(px: u32, dpi: u32) -> f64 {
+    px as f64 * 25.4 / dpi as f64
+}
+
+/// WCAG contrast ratio between two sRGB colors, from 1.0 to 21.0.
+pub fn contrast_ratio(a: Rgb, b: Rgb) -> f64 {
+    fn luminance(c: Rgb) -> f64 {
+        let channel = |v: u8| {
+            let v = v as f64 / 255.0;
+            if v <= 0.03928 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
+        };
+        0.2126 * channel(c.0) + 0.7152 * channel(c.1) + 0.0722 * channel(c.2)
+    }
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Linearly maps `value` from `[low, high]` onto `[0, points]`, clamping at both ends.
+fn ramp(value: f64, low: f64, high: f64, points: f64) -> f64 {
+    ((value - low) / (high - low)).clamp(0.0, 1.0) * points
+}
+
+/// Estimates scanability as a 0-100 score together with suggestions for improving it.
+///
+/// Module size contributes up to 50 points, contrast up to 25, and the error correction level up to 25.
+pub fn score(profile: &ScanProfile) -> (u8, Vec<String>) {
+    let mut suggestions = Vec::new();
+
+    let module = ramp(profile.module_mm, 0.25, 0.75, 50.0);
+    if profile.module_mm < 0.75 {
+        suggestions.push(format!(
+            "Modules are {:.2} mm wide; print at a lower DPI or a larger size so they are at least 0.75 mm.",
+            profile.module_mm
+        ));
+    }
+
+    let ratio = contrast_ratio(profile.colors.dark, profile.colors.light);
+    let contrast = ramp(ratio, 3.0, 7.0, 25.0);
+    if ratio < 7.0 {
+        suggestions.push(format!(
+            "Contrast ratio is {:.1}:1; use darker modules or a lighter background for at least 7:1.",
+            ratio
+        ));
+    }
+
+    let ecl = match profile.ecl {
+        ErrorCorrection::L => 10.0,
+        ErrorCorrection::M => 18.0,
+        ErrorCorrection::Q => 22.0,
+        ErrorCorrection::H => 25.0,
+    };
+    if profile.ecl < ErrorCorrection::Q {
+        suggestions.push("Raise the error correction level (-e Q or -e H) if the code may get scuffed or partially covered.".to_string());
+    }
+
+    ((module + contrast + ecl).round() as u8, suggestions)
+}
+
+/// Minimum quiet zone in modules required by ISO/IEC 18004.
+pub const MIN_QUIET_ZONE: u32 = 4;
+
+/// Parses a distance such as `3m`, `50cm`, or `300mm` into millimetres.
+pub fn parse_distance(s: &str) -> Result<f64, String> {
+    let (value, factor) = if let Some(v) = s.strip_suffix("mm") {
+        (v, 1.0)
+    } else if let Some(v) = s.strip_suffix("cm") {
+        (v, 10.0)
+    } else if let Some(v) = s.strip_suffix('m') {
+        (v, 1000.0)
+    } else {
+        return Err(format!("'{}' needs a unit: mm, cm, or m.", s));
+    };
+    match value.trim().parse::<f64>() {
+        Ok(v) if v > 0.0 && v.is_finite() => Ok(v * factor),
+        _ => Err(format!("'{}' is not a positive distance.", s)),
+    }
+}
+
+/// Smallest module size in millimetres that scans reliably from `distance_mm`.
+///
+/// Follows the common 10:1 rule of thumb: the symbol, excluding the quiet zone, should be at least a tenth of the
+/// scan distance wide.
+pub fn min_module_mm(distance_mm: f64, width_modules: u32) -> f64 {
+    distance_mm / 10.0 / width_modules as f64
+}
+
+/// A single line of a compliance report.
+pub struct Check {
+    pub name: &'static str,
+    pub detail: String,
+    pub passed: bool,
+}
+
+/// Checks quiet zone and module size against QR print guidelines.
+pub fn compliance(quiet_zone: u32, width_modules: u32, module_mm: f64, distance_mm: f64) -> Vec<Check> {
+    let min_module = min_module_mm(distance_mm, width_modules);
+    vec![
+        Check {
+            name: "Quiet zone",
+            detail: format!("{} modules (minimum {})", quiet_zone, MIN_QUIET_ZONE),
+            passed: quiet_zone >= MIN_QUIET_ZONE,
+        },
+        Check {
+            name: "Module size",
+            detail: format!(
+                "{:.2} mm (minimum {:.2} mm at {} mm scan distance)",
+                module_mm, min_module, distance_mm
+            ),
+            passed: module_mm >= min_module,
+        },
+    ]
+}
@@ -0,0 +1,164 @@
+//! Serde support for the core types, enabled with the `serde` feature.
+//!
+//! Values are validated while deserializing, so a config file can never produce an invalid `Wifi`.
+//!
+//! ```
+//! use qrfi::Wifi;
+//!
+//! let wifi: Wifi = serde_json::from_str(r#"{"ssid": "guest", "auth": "WPA", "password": "PASSWORD"}"#).unwrap();
+//! assert_eq!(wifi.to_mecard(), "WIFI:S:guest;T:WPA;P:PASSWORD;H:false;;");
+//! assert_eq!(
+//!     serde_json::to_string(&wifi).unwrap(),
+//!     r#"{"ssid":"guest","auth":"WPA","password":"PASSWORD","hidden":false}"#
+//! );
+//! assert!(serde_json::from_str::<Wifi>(r#"{"ssid": "guest", "password": "short"}"#).is_err());
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{AuthType, EapMethod, Enterprise, Password, Phase2, Ssid, ValidationError, Wifi};
+
+/// Field layout shared by `Password` and `Wifi`.
+#[derive(Serialize)]
+struct Fields<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssid: Option<&'a Ssid>,
+    auth: AuthType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    password: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hidden: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transition_disable: Option<bool>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    enterprise: Option<EnterpriseFields<'a>>,
+}
+
+/// The `WPA2-EAP` settings, inlined into the network.
+#[derive(Serialize)]
+struct EnterpriseFields<'a> {
+    eap: EapMethod,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    phase2: Option<Phase2>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    identity: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    anonymous_identity: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PasswordFields {
+    auth: Option<AuthType>,
+    password: Option<String>,
+}
+impl PasswordFields {
+    /// Builds the password, assuming WPA when only a password is given and `nopass` when neither is.
+    fn validate<E: serde::de::Error>(self) -> Result<Password, E> {
+        let auth_type = self.auth.unwrap_or(if self.password.is_some() { AuthType::Wpa } else { AuthType::Nopass });
+        Password::new(self.password, auth_type).map_err(E::custom)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WifiFields {
+    ssid: Ssid,
+    auth: Option<AuthType>,
+    password: Option<String>,
+    #[serde(default)]
+    hidden: bool,
+    #[serde(default)]
+    transition_disable: bool,
+    eap: Option<EapMethod>,
+    phase2: Option<Phase2>,
+    identity: Option<String>,
+    anonymous_identity: Option<String>,
+}
+
+/// An SSID is a string when it is UTF-8 and a byte sequence otherwise.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SsidRepr {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl Serialize for Ssid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(s) => serializer.serialize_str(s),
+            None => serializer.serialize_bytes(&self.0),
+        }
+    }
+}
+impl<'de> Deserialize<'de> for Ssid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match SsidRepr::deserialize(deserializer)? {
+            SsidRepr::Text(s) => Ssid::new(s),
+            SsidRepr::Bytes(bytes) => Ssid::from_bytes(&bytes),
+        }.map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Password {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Fields { ssid: None, auth: self.auth_type, password: self.value.as_deref(), hidden: None, transition_disable: None, enterprise: None }
+            .serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for Password {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        PasswordFields::deserialize(deserializer)?.validate()
+    }
+}
+
+impl Serialize for Wifi {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Fields {
+            ssid: Some(&self.ssid),
+            auth: self.password.auth_type,
+            password: self.password.value.as_deref(),
+            hidden: Some(self.hidden),
+            // Only written when set, so configs without WPA3 settings keep their shape.
+            transition_disable: Some(true).filter(|_| self.transition_disable),
+            enterprise: self.enterprise.as_ref().map(|enterprise| EnterpriseFields {
+                eap: enterprise.method(),
+                phase2: enterprise.phase2(),
+                identity: enterprise.identity(),
+                anonymous_identity: enterprise.anonymous_identity(),
+            }),
+        }.serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for Wifi {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = WifiFields::deserialize(deserializer)?;
+        let password = PasswordFields { auth: fields.auth, password: fields.password }.validate()?;
+        let wifi = Wifi::new(fields.ssid, password, fields.hidden).with_transition_disable(fields.transition_disable);
+        let Some(method) = fields.eap else {
+            if wifi.password.auth_type == AuthType::Wpa2Eap {
+                return Err(D::Error::custom(ValidationError::MissingEapMethod));
+            }
+            if fields.phase2.is_some() || fields.identity.is_some() || fields.anonymous_identity.is_some() {
+                return Err(D::Error::custom(ValidationError::UnexpectedEapSettings));
+            }
+            return Ok(wifi);
+        };
+        let mut enterprise = Enterprise::new(method);
+        if let Some(phase2) = fields.phase2 {
+            enterprise = enterprise.with_phase2(phase2);
+        }
+        if let Some(identity) = fields.identity {
+            enterprise = enterprise.with_identity(identity);
+        }
+        if let Some(identity) = fields.anonymous_identity {
+            enterprise = enterprise.with_anonymous_identity(identity);
+        }
+        wifi.with_enterprise(enterprise).map_err(D::Error::custom)
+    }
+}
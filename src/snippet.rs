@@ -0,0 +1,67 @@
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Command, ValueEnum};
+
+/// Shells `--prompt-snippet` writes functions for.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Arguments that name a particular network, are secret, pick where one run writes, or change what a run does, so
+/// they never become defaults.
+const SKIPPED: &[&str] = &[
+    "ssid",
+    "ssid_hex",
+    "password",
+    "identity",
+    "anonymous_identity",
+    "output",
+    "audit_log",
+    "minisign_key",
+    "bundle",
+    "export",
+    "check",
+    "interactive",
+    "prompt_snippet",
+];
+
+/// A `wifi-qr` function for `shell` that runs qrfi with every option given on this command line as a default.
+///
+/// The options are read back through `command`, the clap definition the arguments were parsed with, so the
+/// snippet spells them exactly as qrfi accepts them.
+pub fn render(shell: Shell, command: &Command, matches: &ArgMatches) -> String {
+    let mut defaults = Vec::new();
+    for arg in command.get_arguments() {
+        let id = arg.get_id().as_str();
+        let Some(long) = arg.get_long() else { continue };
+        if SKIPPED.contains(&id) || matches.value_source(id) != Some(ValueSource::CommandLine) {
+            continue;
+        }
+        if !arg.get_action().takes_values() {
+            defaults.push(format!("--{}", long));
+            continue;
+        }
+        for value in matches.get_raw(id).into_iter().flatten() {
+            defaults.push(quote(shell, &format!("--{}={}", long, value.to_string_lossy())));
+        }
+    }
+    let invocation = defaults.iter().fold("qrfi".to_string(), |line, arg| line + " " + arg);
+    let header = format!("# Generated by qrfi {}; rerun `qrfi --prompt-snippet` to update.", env!("CARGO_PKG_VERSION"));
+    match shell {
+        Shell::Bash | Shell::Zsh => format!("{}\nwifi-qr() {{\n    {} \"$@\"\n}}\n", header, invocation),
+        Shell::Fish => format!("{}\nfunction wifi-qr\n    {} $argv\nend\n", header, invocation),
+    }
+}
+
+/// Quotes `s` as one word for `shell`, leaving plain words as they are.
+fn quote(shell: Shell, s: &str) -> String {
+    if s.chars().all(|c| c.is_ascii_alphanumeric() || "-_=.,/:#+".contains(c)) {
+        return s.to_string();
+    }
+    match shell {
+        Shell::Bash | Shell::Zsh => format!("'{}'", s.replace('\'', r"'\''")),
+        Shell::Fish => format!("'{}'", s.replace('\\', r"\\").replace('\'', r"\'")),
+    }
+}
@@ -0,0 +1,108 @@
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+
+use clap::ValueEnum;
+use qrfi::{AuthType, EapMethod, Password, Ssid};
+
+use crate::Args;
+
+/// Prints `question` to stderr and reads one line of the answer from stdin.
+///
+/// An empty answer returns `default`; end of input or a line longer than `limit` bytes is an error.
+fn ask(question: &str, default: Option<&str>, limit: u64) -> io::Result<String> {
+    match default {
+        Some(default) => eprint!("{} [{}]: ", question, default),
+        None => eprint!("{}: ", question),
+    }
+    io::stderr().flush()?;
+    let mut line = String::new();
+    if io::stdin().lock().take(limit).read_line(&mut line)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Input ended before all questions were answered."));
+    }
+    if !line.ends_with('\n') && line.len() as u64 == limit {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Answer is longer than {} bytes; raise --max-input-bytes to accept it.", limit)));
+    }
+    let answer = line.trim_end_matches(['\n', '\r']);
+    Ok(match (answer, default) {
+        ("", Some(default)) => default.to_string(),
+        _ => answer.to_string(),
+    })
+}
+
+/// Reads a password without echoing it when stdin is a terminal.
+fn ask_secret(question: &str, limit: u64) -> io::Result<String> {
+    if io::stdin().is_terminal() {
+        rpassword::prompt_password(format!("{}: ", question))
+    } else {
+        ask(question, None, limit)
+    }
+}
+
+/// Asks for the SSID, authentication type, EAP method and identity (WPA2-EAP only), password, and hidden flag one
+/// line at a time.
+///
+/// Each answer is validated right away and asked again until it is valid. Answers are limited to
+/// `--max-input-bytes` like the rest of stdin.
+pub fn run(args: &mut Args) -> io::Result<()> {
+    let limit = args.max_input_bytes;
+    loop {
+        let ssid = ask("SSID", args.ssid.as_deref(), limit)?;
+        match Ssid::from_bytes_with_policy(ssid.as_bytes(), args.validation) {
+            Ok(_) => {
+                args.ssid = Some(ssid);
+                break;
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+    loop {
+        let default = args.authentication_type.to_string();
+        let answer = ask("Authentication type (WPA, SAE, WEP, WPA2-EAP, OWE, nopass)", Some(&default), limit)?;
+        match answer.parse::<AuthType>() {
+            Ok(auth_type) => {
+                args.authentication_type = auth_type;
+                break;
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+    if args.authentication_type == AuthType::Wpa2Eap {
+        loop {
+            let default = args.eap.map(|method| method.to_string());
+            let answer = ask("EAP method (PEAP, TLS, TTLS, PWD, SIM, AKA, AKA')", default.as_deref(), limit)?;
+            match EapMethod::from_str(&answer, true) {
+                Ok(method) => {
+                    args.eap = Some(method);
+                    break;
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+        let identity = ask("Identity (empty for none)", args.identity.as_deref(), limit)?;
+        args.identity = Some(identity);
+    }
+    if !matches!(args.authentication_type, AuthType::Nopass | AuthType::Owe) {
+        loop {
+            let password = ask_secret("Password", limit)?;
+            match Password::new_with_policy(Some(password.clone()), args.authentication_type, args.validation) {
+                Ok(_) => {
+                    args.password = Some(password);
+                    break;
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+    }
+    loop {
+        let default = if args.hidden { "y" } else { "n" };
+        match ask("Hidden network? (y/n)", Some(default), limit)?.to_ascii_lowercase().as_str() {
+            "y" | "yes" => args.hidden = true,
+            "n" | "no" => args.hidden = false,
+            _ => {
+                eprintln!("Please answer y or n.");
+                continue;
+            }
+        }
+        break;
+    }
+    Ok(())
+}
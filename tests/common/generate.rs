@@ -1,6 +1,7 @@
 use rand::Rng;
 use rand::seq::SliceRandom;
 
+#[allow(clippy::enum_variant_names)]
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub enum CharType {
     DoubleByte,
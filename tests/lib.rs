@@ -60,6 +60,35 @@ fn mecardify_preserves_nondelimiters() {
     }
 }
 
+#[test]
+fn escape_borrows_unless_something_changes() {
+    use std::borrow::Cow;
+    assert!(matches!(mecardify("guest"), Cow::Borrowed(_)));
+    assert!(matches!(mecardify("a;b"), Cow::Owned(_)));
+    assert!(matches!(Ssid::new("guest".to_string()).unwrap().escape(), Cow::Borrowed(_)));
+    assert!(matches!(Ssid::new("cafe".to_string()).unwrap().escape(), Cow::Owned(_)));
+    let key = Password::new(Some(generate_random_hex(64)), AuthType::Wpa).unwrap();
+    assert!(matches!(key.escape(), Cow::Borrowed(_)));
+    let passphrase = Password::new(Some("12345678".to_string()), AuthType::Wpa).unwrap();
+    assert_eq!(passphrase.escape(), "\"12345678\"");
+}
+
+#[test]
+fn mecard_unescape_reverses_mecardify() {
+    for _ in 0..100 {
+        let input = generate_random_ascii(16) + ",:;\\" + &generate_random_mbstring(16, &[DoubleByte, TripleByte, QuadrupleByte]);
+        assert_eq!(mecard_unescape(&mecardify(&input)), Ok(input.clone()), "{:?} should round-trip", input);
+    }
+}
+
+#[test]
+fn mecard_unescape_rejects_dangling_escape() {
+    let cases = vec!["\\", "abc\\", "\\\\\\"];
+    for input in cases {
+        assert_eq!(mecard_unescape(input), Err(ParseError::DanglingEscape), "{:?} should be rejected", input);
+    }
+}
+
 #[test]
 fn ssid_validate_rejects_empty_input() {
     let input = "".to_string();
@@ -99,6 +128,66 @@ fn ssid_validate_rejects_excessive_length() {
     }
 }
 
+#[test]
+fn ssid_from_bytes_emits_hex_unless_utf8() {
+    let cases: Vec<(&[u8], Option<&str>, &str)> = vec![
+        (b"guest", Some("guest"), "guest"),
+        (b"a;b", Some("a;b"), "a\\;b"),
+        (b"caf\xe9", None, "636166e9"),
+        (b"\xff\x00", None, "ff00"),
+    ];
+    for (input, text, escaped) in cases {
+        let ssid = Ssid::from_bytes(input).unwrap();
+        assert_eq!(ssid.as_str(), text, "{:?}", input);
+        assert_eq!(ssid.as_bytes(), input);
+        assert_eq!(ssid.escape(), escaped, "{:?}", input);
+    }
+    assert_eq!(Ssid::from_bytes(&[0xff; 33]).err(), Some(ValidationError::SsidTooLong { bytes: 33 }));
+}
+
+#[test]
+fn wifi_with_hex_ssid_emits_every_ssid_in_hex() {
+    let raw_ssid = generate_random_mbstring(8, &[DoubleByte, TripleByte]);
+    let wifi = WifiBuilder::new(raw_ssid.clone()).build().unwrap().with_hex_ssid(true);
+    let hex: String = raw_ssid.bytes().map(|byte| format!("{:02x}", byte)).collect();
+    assert_eq!(wifi.to_mecard(), format!("WIFI:S:{};T:nopass;P:;H:false;;", hex));
+    let parsed = Wifi::from_mecard(&wifi.to_mecard()).unwrap();
+    assert_eq!(parsed.ssid().as_str(), Some(raw_ssid.as_str()));
+    assert_eq!(parsed.to_mecard(), wifi.to_mecard());
+}
+
+#[test]
+fn wifi_from_mecard_decodes_hex_ssids() {
+    let binary = Wifi::new(Ssid::from_bytes(b"caf\xe9").unwrap(), Password::new(None, AuthType::Nopass).unwrap(), false);
+    let parsed = Wifi::from_mecard(&binary.to_mecard()).unwrap();
+    assert_eq!(parsed.ssid().as_bytes(), b"caf\xe9");
+    assert_eq!(parsed.to_mecard(), binary.to_mecard());
+    // Odd-length and quoted hex stay text.
+    assert_eq!(Wifi::from_mecard("WIFI:S:abc;;").unwrap().ssid().as_str(), Some("abc"));
+    assert_eq!(Wifi::from_mecard(r#"WIFI:S:"cafe";;"#).unwrap().ssid().as_str(), Some("cafe"));
+}
+
+#[test]
+fn wifi_to_mecard_quotes_hex_lookalikes() {
+    let wpa_key = "0123456789abcdef".repeat(4);
+    let cases = vec![
+        ("ABCDEF1234", Some("12345678"), AuthType::Wpa, r#"WIFI:S:"ABCDEF1234";T:WPA;P:"12345678";H:false;;"#),
+        ("guest", Some(wpa_key.as_str()), AuthType::Wpa, "WIFI:S:guest;T:WPA;P:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef;H:false;;"),
+        ("cafe", Some("0123456789"), AuthType::Wep, r#"WIFI:S:"cafe";T:WEP;P:0123456789;H:false;;"#),
+        ("guest", Some("12345"), AuthType::Wep, r#"WIFI:S:guest;T:WEP;P:"12345";H:false;;"#),
+        ("guest-1", Some("deadbeef!"), AuthType::Wpa, "WIFI:S:guest-1;T:WPA;P:deadbeef!;H:false;;"),
+    ];
+    for (ssid, password, auth_type, expected) in cases {
+        let wifi = Wifi::new(Ssid::new(ssid.to_string()).unwrap(), Password::new(password.map(String::from), auth_type).unwrap(), false);
+        assert_eq!(wifi.to_mecard(), expected, "{:?}", ssid);
+        let parsed = Wifi::from_mecard(expected).unwrap();
+        assert_eq!(parsed.ssid().as_str(), Some(ssid));
+        assert_eq!(parsed.password().expose_secret(), password);
+    }
+    let quoted = Wifi::from_mecard(r#"WIFI:S:"guest";T:nopass;;"#).unwrap();
+    assert_eq!(quoted.ssid().as_str(), Some(r#""guest""#));
+}
+
 #[test]
 fn ssid_password_validate_accepts_valid_wpa_passphrase() {
     let cases = vec![
@@ -162,6 +251,32 @@ fn ssid_password_validate_forces_none_if_authtype_is_nopass() {
     assert!(p.is_ok(), "Nopass constructor should handle and accept provided strings by forcing None");
 }
 
+#[test]
+fn debug_output_masks_password() {
+    let raw_pass = generate_random_hex(64);
+    let wifi = WifiBuilder::new("guest").wpa(raw_pass.clone()).build().unwrap();
+    let debug = format!("{:?}", wifi);
+    assert!(!debug.contains(&raw_pass), "Debug output leaks the password: {}", debug);
+    assert!(debug.contains(r#"value: Some("********"), len: 64"#), "{}", debug);
+    assert!(debug.contains(r#"Ssid("guest")"#), "{}", debug);
+    assert_eq!(wifi.password().expose_secret(), Some(raw_pass.as_str()));
+    let builder = format!("{:?}", WifiBuilder::new("guest").wpa(raw_pass.clone()));
+    assert!(!builder.contains(&raw_pass), "Debug output leaks the password: {}", builder);
+}
+
+#[test]
+fn validation_errors_report_structured_kinds() {
+    let cases = vec![
+        (Ssid::new("".to_string()).err(), ValidationError::EmptySsid),
+        (Ssid::new(generate_random_ascii(33)).err(), ValidationError::SsidTooLong { bytes: 33 }),
+        (Password::new(Some(generate_random_ascii(7)), AuthType::Wpa).err(), ValidationError::InvalidWpaPassphrase { bytes: 7, policy: ValidationPolicy::Strict }),
+        (Password::new(Some(generate_random_hex(11)), AuthType::Wep).err(), ValidationError::InvalidWepKey { bytes: 11, policy: ValidationPolicy::Strict }),
+    ];
+    for (actual, expected) in cases {
+        assert_eq!(actual, Some(expected.clone()), "Expected {:?}", expected);
+    }
+}
+
 #[test]
 fn wifi_to_mecard_matches_expected_structure_with_random_inputs() {
     // Check whether the logic for generating the MECARD format matches the description in this test function
@@ -188,3 +303,536 @@ fn wifi_to_mecard_matches_expected_structure_with_random_inputs() {
         );
     }
 }
+
+#[test]
+fn wifi_to_mecard_into_reuses_the_buffer() {
+    let mut buffer = String::with_capacity(256);
+    let capacity = buffer.capacity();
+    for _ in 0..100 {
+        let ssid = Ssid::new(generate_random_mbstring(8, &[DoubleByte, TripleByte, QuadrupleByte])).unwrap();
+        let password = Password::new(Some(generate_random_ascii(63)), AuthType::Wpa).unwrap();
+        let wifi = Wifi::new(ssid, password, rand::thread_rng().gen_bool(0.5));
+        buffer.clear();
+        let out: &mut dyn std::fmt::Write = &mut buffer;
+        wifi.to_mecard_into(out).unwrap();
+        assert_eq!(buffer, wifi.to_mecard());
+    }
+    assert_eq!(buffer.capacity(), capacity);
+}
+
+#[test]
+fn wifi_from_mecard_round_trips_random_inputs() {
+    for _ in 0..100 {
+        let raw_ssid = generate_random_ascii(32);
+        let raw_pass = generate_random_ascii(16);
+        let is_hidden = rand::thread_rng().gen_bool(0.5);
+        let ssid = Ssid::new(raw_ssid.clone()).unwrap();
+        let password = Password::new(Some(raw_pass.clone()), AuthType::Wpa).unwrap();
+        let mecard = Wifi::new(ssid, password, is_hidden).to_mecard();
+        let parsed = Wifi::from_mecard(&mecard).unwrap_or_else(|e| panic!("{:?} should parse: {}", mecard, e));
+        assert_eq!(parsed.ssid().as_str(), Some(raw_ssid.as_str()));
+        assert_eq!(parsed.password().expose_secret(), Some(raw_pass.as_str()));
+        assert_eq!(parsed.hidden(), is_hidden);
+        assert_eq!(parsed.to_mecard(), mecard);
+    }
+}
+
+#[test]
+fn wifi_from_mecard_accepts_any_field_order_and_missing_fields() {
+    let cases = vec![
+        ("WIFI:P:password;S:guest;;", "WIFI:S:guest;T:WPA;P:password;H:false;;"),
+        ("WIFI:H:true;S:guest;T:nopass;;", "WIFI:S:guest;T:nopass;P:;H:true;;"),
+        ("WIFI:S:guest;", "WIFI:S:guest;T:nopass;P:;H:false;;"),
+        (r"WIFI:S:a\;b;T:WEP;P:12345;X:ignored;;", r#"WIFI:S:a\;b;T:WEP;P:"12345";H:false;;"#),
+        ("WIFI:S:x;T:;P:secret12;;", "WIFI:S:x;T:WPA;P:secret12;H:false;;"),
+        ("WIFI:S:guest;T:;H:;;", "WIFI:S:guest;T:nopass;P:;H:false;;"),
+        ("WIFI:S:guest;T:WPA;P:password;R:;;", "WIFI:S:guest;T:WPA;P:password;H:false;;"),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(Wifi::from_mecard(input).map(|wifi| wifi.to_mecard()), Ok(expected.to_string()), "{:?}", input);
+    }
+}
+
+#[test]
+fn wifi_from_mecard_rejects_malformed_input() {
+    let cases = vec![
+        ("S:guest;;", ParseError::MissingPrefix),
+        ("WIFI:T:WPA;P:password;;", ParseError::MissingSsid),
+        ("WIFI:S:guest;oops;;", ParseError::MalformedField("oops".to_string())),
+        ("WIFI:S:guest\\", ParseError::DanglingEscape),
+        ("WIFI:S:guest;T:WPA4;;", ParseError::UnknownAuthType("WPA4".to_string())),
+        ("WIFI:S:guest;H:maybe;;", ParseError::InvalidHidden("maybe".to_string())),
+        ("WIFI:S:guest;T:WPA;P:password;R:x;;", ParseError::InvalidTransitionDisable("x".to_string())),
+        ("WIFI:S:guest;T:WPA;P:short;;", ParseError::Invalid(ValidationError::InvalidWpaPassphrase { bytes: 5, policy: ValidationPolicy::Strict })),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(Wifi::from_mecard(input).err(), Some(expected), "{:?}", input);
+    }
+}
+
+#[test]
+fn wifi_emits_sae_and_transition_disable() {
+    let raw_pass = generate_random_ascii(100);
+    let wifi = WifiBuilder::new("lobby").sae(raw_pass.clone()).transition_disable(true).build().unwrap();
+    let parsed = Wifi::from_mecard(&wifi.to_mecard()).unwrap();
+    assert_eq!(parsed.password().auth_type(), AuthType::Sae);
+    assert_eq!(parsed.password().expose_secret(), Some(raw_pass.as_str()));
+    assert!(parsed.transition_disable());
+    assert!(wifi.with_sae_as_wpa(true).to_mecard().starts_with("WIFI:S:lobby;T:WPA;P:"));
+
+    assert!(Wifi::from_mecard("WIFI:S:lobby;T:WPA;P:password;R:3;;").unwrap().transition_disable());
+    assert!(!Wifi::from_mecard("WIFI:S:lobby;T:WPA;P:password;R:2;;").unwrap().transition_disable());
+    let open = WifiBuilder::new("lobby").transition_disable(true).build().unwrap();
+    assert_eq!(open.to_mecard(), "WIFI:S:lobby;T:nopass;P:;H:false;;");
+    assert_eq!(WifiBuilder::new("lobby").sae("").build().err(), Some(ValidationError::EmptySaePassword));
+}
+
+#[test]
+fn wifi_distinguishes_owe_from_nopass() {
+    let wifi = WifiBuilder::new("lobby").wpa("password").owe().build().unwrap();
+    assert_eq!(wifi.to_mecard(), "WIFI:S:lobby;T:OWE;P:;H:false;;");
+    assert_eq!(Wifi::from_mecard(&wifi.to_mecard()).unwrap().password().auth_type(), AuthType::Owe);
+    assert_eq!(
+        Password::new(Some(generate_random_ascii(16)), AuthType::Owe).err(),
+        Some(ValidationError::UnexpectedPassword)
+    );
+    assert_eq!(Wifi::validate_all(b"lobby", Some("password"), AuthType::Owe), [ValidationError::UnexpectedPassword]);
+}
+
+#[test]
+fn wifi_payloads_fill_their_buffers_exactly() {
+    let enterprise = Enterprise::new(EapMethod::Peap).with_phase2(Phase2::Mschapv2).with_identity("").with_anonymous_identity("");
+    let eap = WifiBuilder::new("0123").eap(enterprise, Some("abcdef".to_string())).hidden(true).build().unwrap();
+    let sae = WifiBuilder::new("a;b").sae("50% off").build().unwrap().with_transition_disable(true);
+    for payload in [eap.to_mecard(), sae.to_mecard(), sae.to_uri()] {
+        assert_eq!(payload.capacity(), payload.len(), "{}", payload);
+    }
+}
+
+#[test]
+fn wifi_round_trips_wpa2_eap_settings() {
+    let enterprise = Enterprise::new(EapMethod::Ttls)
+        .with_phase2(Phase2::Pap)
+        .with_identity("alice;corp")
+        .with_anonymous_identity("anonymous");
+    let wifi = WifiBuilder::new("campus").eap(enterprise.clone(), Some("pass:word".to_string())).build().unwrap();
+    let mecard = wifi.to_mecard();
+    assert_eq!(mecard, r"WIFI:S:campus;T:WPA2-EAP;P:pass\:word;E:TTLS;PH2:PAP;I:alice\;corp;A:anonymous;H:false;;");
+    let parsed = Wifi::from_mecard(&mecard).unwrap();
+    assert_eq!(parsed.enterprise(), Some(&enterprise));
+    assert_eq!(parsed.password().expose_secret(), Some("pass:word"));
+
+    let tls = WifiBuilder::new("campus").eap(Enterprise::new(EapMethod::Tls), Some(String::new())).build().unwrap();
+    assert_eq!(tls.password().expose_secret(), None);
+    assert_eq!(Wifi::from_mecard("WIFI:S:guest;T:WPA;P:password;E:PEAP;;").unwrap().enterprise(), None);
+}
+
+#[test]
+fn wifi_rejects_inconsistent_wpa2_eap_settings() {
+    let cases = vec![
+        ("WIFI:S:campus;T:WPA2-EAP;P:password;;", ParseError::Invalid(ValidationError::MissingEapMethod)),
+        ("WIFI:S:campus;T:WPA2-EAP;E:FAST;;", ParseError::UnknownEapMethod("FAST".to_string())),
+        ("WIFI:S:campus;T:WPA2-EAP;E:TTLS;PH2:CHAP;;", ParseError::UnknownPhase2("CHAP".to_string())),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(Wifi::from_mecard(input).err(), Some(expected), "{:?}", input);
+    }
+    let wpa = WifiBuilder::new("guest").wpa("password").build().unwrap();
+    assert_eq!(wpa.with_enterprise(Enterprise::new(EapMethod::Peap)).err(), Some(ValidationError::UnexpectedEapSettings));
+}
+
+#[test]
+fn wifi_to_uri_percent_encodes_values() {
+    let cases = [
+        (WifiBuilder::new("SSID").wpa("PASSWORD").build().unwrap(), "WIFI:T:WPA;S:SSID;P:PASSWORD;;"),
+        (WifiBuilder::new(r"a;b,c:d\e").wpa("100%;sure").build().unwrap(), r"WIFI:T:WPA;S:a%3Bb,c:d\e;P:100%25%3Bsure;;"),
+        (WifiBuilder::new("café").hidden(true).build().unwrap(), "WIFI:S:caf%C3%A9;H:true;;"),
+        (WifiBuilder::new("lobby").owe().build().unwrap(), "WIFI:T:OWE;S:lobby;;"),
+        (WifiBuilder::new("lobby").wep("12345").build().unwrap().with_transition_disable(true), "WIFI:T:WEP;S:lobby;P:12345;;"),
+    ];
+    for (wifi, uri) in cases {
+        assert_eq!(wifi.to_uri(), uri);
+    }
+    let binary = Wifi::new(Ssid::from_bytes(b"caf\xe9").unwrap(), Password::new(None, AuthType::Nopass).unwrap(), false);
+    assert_eq!(binary.with_hex_ssid(true).to_uri(), "WIFI:S:caf%E9;;");
+}
+
+#[test]
+fn validation_policy_relaxes_checks() {
+    let utf8 = "Passwört!";
+    assert_eq!(WifiBuilder::new("lobby").wpa(utf8).build().err(), Some(ValidationError::InvalidWpaPassphrase { bytes: 10, policy: ValidationPolicy::Strict }));
+    let lenient = WifiBuilder::new("lobby").wpa(utf8).policy(ValidationPolicy::Lenient).build().unwrap();
+    assert_eq!(lenient.to_mecard(), "WIFI:S:lobby;T:WPA;P:Passwört!;H:false;;");
+    for policy in [ValidationPolicy::Strict, ValidationPolicy::Lenient] {
+        assert_eq!(Ssid::from_bytes_with_policy(b"", policy).err(), Some(ValidationError::EmptySsid));
+        assert_eq!(
+            Wifi::validate_all_with_policy(&[b'x'; 33], Some("short"), AuthType::Wpa, policy),
+            [ValidationError::SsidTooLong { bytes: 33 }, ValidationError::InvalidWpaPassphrase { bytes: 5, policy }]
+        );
+    }
+    let short = Password::new_with_policy(Some("kurz".to_string()), AuthType::Wpa, ValidationPolicy::Lenient).err().unwrap();
+    assert_eq!(short.to_string(), "WPA passphrase must be 8-63 bytes of UTF-8, or 64 hex digits.");
+    assert!(Wifi::validate_all_with_policy(&[b'x'; 33], Some("short"), AuthType::Wpa, ValidationPolicy::Off).is_empty());
+    assert!(Password::new_with_policy(Some(String::new()), AuthType::Sae, ValidationPolicy::Off).is_ok());
+}
+
+#[test]
+fn lenient_validation_accepts_extended_wep_keys() {
+    for len in [16, 29, 32, 58] {
+        let key = "0123456789abcdef".repeat(4)[..len].to_string();
+        assert_eq!(Password::new(Some(key.clone()), AuthType::Wep).err(), Some(ValidationError::InvalidWepKey { bytes: len, policy: ValidationPolicy::Strict }));
+        let password = Password::new_with_policy(Some(key), AuthType::Wep, ValidationPolicy::Lenient).unwrap();
+        // Hex keys are left bare, passphrases that look like hex are quoted.
+        assert_eq!(password.escape().starts_with('"'), [16, 29].contains(&len));
+    }
+    let key = "g".repeat(32);
+    let err = Password::new_with_policy(Some(key), AuthType::Wep, ValidationPolicy::Lenient).err().unwrap();
+    assert_eq!(err.to_string(), "WEP password must be 5, 13, 16, or 29 characters, or 10, 26, 32, or 58 hex digits.");
+}
+
+#[test]
+fn compat_profiles_pin_their_layout() {
+    let wpa = || WifiBuilder::new("SSID").wpa("PASSWORD").build().unwrap();
+    let open = || WifiBuilder::new("SSID").hidden(true).build().unwrap();
+    let cases = [
+        (CompatProfile::Zxing, "WIFI:S:SSID;T:WPA;P:PASSWORD;H:false;;", "WIFI:S:SSID;T:nopass;P:;H:true;;"),
+        (CompatProfile::Ios, "WIFI:T:WPA;S:SSID;P:PASSWORD;;", "WIFI:T:nopass;S:SSID;H:true;;"),
+        (CompatProfile::Android, "WIFI:S:SSID;T:WPA;P:PASSWORD;;", "WIFI:S:SSID;T:nopass;P:;H:true;;"),
+        (CompatProfile::Loose, "WIFI:S:SSID;T:WPA;P:PASSWORD;", "WIFI:S:SSID;T:nopass;H:true;"),
+    ];
+    for (profile, wpa_mecard, open_mecard) in cases {
+        for (wifi, expected) in [(wpa(), wpa_mecard), (open(), open_mecard)] {
+            let mecard = wifi.with_compat_profile(profile).to_mecard();
+            assert_eq!(mecard, expected);
+            assert_eq!(Wifi::from_mecard(&mecard).unwrap().to_mecard(), Wifi::from_mecard(expected).unwrap().to_mecard());
+        }
+    }
+    let sae = WifiBuilder::new("SSID").sae("PASSWORD").transition_disable(true).build().unwrap();
+    assert_eq!(sae.with_compat_profile(CompatProfile::Ios).to_mecard(), "WIFI:T:SAE;S:SSID;P:PASSWORD;R:1;;");
+}
+
+#[test]
+fn wifi_from_mecard_rejects_oversized_input() {
+    let fits = format!("WIFI:S:guest;X:{};;", "\\;".repeat((Wifi::MAX_MECARD_LEN - 17) / 2));
+    assert!(Wifi::from_mecard(&fits).is_ok());
+    let huge = format!("WIFI:S:guest;X:{};;", "\\".repeat(1 << 20));
+    assert_eq!(Wifi::from_mecard(&huge).err(), Some(ParseError::TooLong { bytes: huge.len() }));
+    assert_eq!(mecard_unescape(&huge).err(), Some(ParseError::TooLong { bytes: huge.len() }));
+}
+
+#[test]
+fn mecard_macro_matches_to_mecard() {
+    const KEY: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+    let cases = [
+        (qrfi::mecard!("SSID", "PASSWORD"), WifiBuilder::new("SSID").wpa("PASSWORD")),
+        (qrfi::mecard!(r"a;b,c:d\e", r"p;a,s:s\w"), WifiBuilder::new(r"a;b,c:d\e").wpa(r"p;a,s:s\w")),
+        (qrfi::mecard!("cafe", "12345678", true), WifiBuilder::new("cafe").wpa("12345678").hidden(true)),
+        (qrfi::mecard!("SSID", KEY), WifiBuilder::new("SSID").wpa(KEY)),
+        (qrfi::mecard!("カフェ"), WifiBuilder::new("カフェ")),
+    ];
+    for (payload, builder) in cases {
+        assert_eq!(payload, builder.build().unwrap().to_mecard());
+    }
+}
+
+#[test]
+fn parsers_never_panic_on_adversarial_input() {
+    let mut rng = rand::thread_rng();
+    let alphabet = ['W', 'I', 'F', ':', ';', ',', '\\', '"', 'S', 'T', 'P', 'H', 'E', 'R', '1', 'a', 'é', '🦀', '\0'];
+    for _ in 0..2000 {
+        let len = rng.gen_range(0..64);
+        let body: String = (0..len).map(|_| alphabet[rng.gen_range(0..alphabet.len())]).collect();
+        for input in [body.clone(), format!("WIFI:{}", body)] {
+            let _ = Wifi::from_mecard(&input);
+            let _ = mecard_unescape(&input);
+            let _ = input.parse::<AuthType>();
+            let _ = input.parse::<EapMethod>();
+            let _ = input.parse::<Rgb>();
+        }
+    }
+    let bytes: Vec<u8> = (0..64).map(|_| rng.r#gen()).collect();
+    let _ = Wifi::from_mecard(&String::from_utf8_lossy(&bytes));
+    let _ = Ssid::from_bytes(&bytes).map(|ssid| ssid.escape().into_owned());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn renderers_reject_impossible_sizes_without_panicking() {
+    let options = RenderOptions::default();
+    let err = |modules: &[bool], width| write_svg(modules, width, &options, std::io::sink()).unwrap_err().kind();
+    assert_eq!(err(&[], 0), std::io::ErrorKind::InvalidInput);
+    assert_eq!(err(&[true; 3], 2), std::io::ErrorKind::InvalidInput);
+    assert_eq!(err(&[true], usize::MAX), std::io::ErrorKind::InvalidInput);
+    let huge = RenderOptions { scale: u32::MAX, margin: u32::MAX, ..RenderOptions::default() };
+    assert_eq!(write_svg(&[true], 1, &huge, std::io::sink()).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+
+    let qr = WifiBuilder::new("SSID").build().unwrap().to_qr(ErrorCorrection::M).unwrap();
+    assert!(!qr.is_dark(qr.width(), 0));
+    assert!(!qr.is_dark(0, usize::MAX));
+    assert!(AsciiRenderer.render(&qr, &huge, &mut std::io::sink()).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn wifi_modules_match_the_matrix() {
+    let wifi = WifiBuilder::new(generate_random_ascii(16)).wpa(generate_random_ascii(16)).build().unwrap();
+    let qr = wifi.to_qr(ErrorCorrection::Q).unwrap();
+    let modules: Vec<_> = wifi.modules(ErrorCorrection::Q).unwrap().collect();
+    assert_eq!(modules.len(), qr.width() * qr.width());
+    assert!(modules.iter().all(|&(x, y, dark)| qr.is_dark(x, y) == dark));
+    assert_eq!(modules, qr.iter().collect::<Vec<_>>());
+}
+
+#[cfg(feature = "led")]
+#[test]
+fn led_matrix_draws_scaled_and_centered() {
+    struct Panel { columns: usize, rows: usize, lit: Vec<bool>, flushed: bool }
+    impl LedMatrix for Panel {
+        type Error = String;
+        fn size(&self) -> (usize, usize) {
+            (self.columns, self.rows)
+        }
+        fn set(&mut self, x: usize, y: usize, on: bool) -> Result<(), Self::Error> {
+            self.lit[y * self.columns + x] = on;
+            Ok(())
+        }
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            self.flushed = true;
+            Ok(())
+        }
+    }
+    let qr = WifiBuilder::new("SSID").build().unwrap().to_qr(ErrorCorrection::L).unwrap();
+    let mut panel = Panel { columns: 80, rows: 64, lit: vec![true; 80 * 64], flushed: false };
+    panel.draw(&qr, 4).unwrap();
+    assert!(panel.flushed);
+    let modules = qr.width() + 8;
+    let scale = 64 / modules;
+    let (left, top) = ((80 - modules * scale) / 2, (64 - modules * scale) / 2);
+    for (x, y, dark) in qr.iter() {
+        for (dx, dy) in [(0, 0), (scale - 1, 0), (0, scale - 1), (scale - 1, scale - 1)] {
+            let (px, py) = (left + (4 + x) * scale + dx, top + (4 + y) * scale + dy);
+            assert_eq!(panel.lit[py * 80 + px], !dark);
+        }
+    }
+    assert!(panel.lit[top * 80 + left] && !panel.lit[top * 80 + left - 1]);
+
+    let mut small = Panel { columns: modules - 1, rows: 64, lit: vec![false; (modules - 1) * 64], flushed: false };
+    assert_eq!(small.draw(&qr, 4), Err(LedError::TooSmall { modules, columns: modules - 1, rows: 64 }));
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn parser_corpus_round_trips_whatever_parses() {
+    for input in PARSER_CORPUS {
+        if let Ok(wifi) = Wifi::from_mecard(input) {
+            let mecard = wifi.to_mecard();
+            assert_eq!(Wifi::from_mecard(&mecard).map(|wifi| wifi.to_mecard()), Ok(mecard), "{:?}", input);
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn dpp_validates_bootstrapping_fields() {
+    const KEY: &str = "MDkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDIgADURzxmttZoIRIPWGoQMV00XHWCAQIhXruVWOz0NjlkIA=";
+    assert_eq!(Dpp::new(KEY).unwrap().to_uri(), format!("DPP:K:{};;", KEY));
+    let dpp = Dpp::new(KEY).unwrap().with_info("Lamp 2").unwrap().with_channels(vec![Channel::new(81, 6).unwrap()]);
+    assert_eq!(dpp.to_uri(), format!("DPP:C:81/6;I:Lamp 2;K:{};;", KEY));
+    for key in ["", "====", "abc", "ab;c", "a===", &format!("{}=", KEY)] {
+        assert_eq!(Dpp::new(key).err(), Some(DppError::InvalidPublicKey), "{:?}", key);
+    }
+    assert_eq!(Channel::new(81, 0).err(), Some(DppError::InvalidChannel { class: 81, channel: 0 }));
+    assert_eq!(Dpp::new(KEY).unwrap().with_info("a;b").err(), Some(DppError::InvalidInfo));
+    assert_eq!(Dpp::new(KEY).unwrap().with_info("").unwrap().info(), None);
+}
+
+#[test]
+fn wifi_round_trips_through_display_and_from_str() {
+    let raw_ssid = generate_random_mbstring(16, &[DoubleByte, TripleByte, QuadrupleByte]);
+    let raw_pass = generate_random_ascii(16);
+    let wifi = WifiBuilder::new(raw_ssid).wep(raw_pass[..13].to_string()).build().unwrap();
+    let text = wifi.to_string();
+    assert_eq!(text, wifi.to_mecard());
+    let parsed: Wifi = text.parse().unwrap();
+    assert_eq!(format!("{}", parsed), text);
+    assert_eq!("S:guest;;".parse::<Wifi>().err(), Some(ParseError::MissingPrefix));
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn wifi_to_mecard_zeroizing_matches_to_mecard() {
+    let wifi = WifiBuilder::new(generate_random_ascii(32)).wpa(generate_random_ascii(63)).build().unwrap();
+    assert_eq!(*wifi.to_mecard_zeroizing(), wifi.to_mecard());
+}
+
+#[test]
+fn wifi_builder_matches_manual_construction() {
+    let raw_ssid = generate_random_mbstring(16, &[DoubleByte, TripleByte]);
+    let raw_pass = generate_random_ascii(16);
+    let built = WifiBuilder::new(raw_ssid.clone()).wpa(raw_pass.clone()).hidden(true).build().unwrap();
+    let manual = Wifi::new(
+        Ssid::new(raw_ssid).unwrap(),
+        Password::new(Some(raw_pass), AuthType::Wpa).unwrap(),
+        true,
+    );
+    assert_eq!(built.to_mecard(), manual.to_mecard());
+}
+
+#[test]
+fn wifi_builder_validates_on_build() {
+    let cases = vec![
+        (WifiBuilder::new("").build().err(), ValidationError::EmptySsid),
+        (WifiBuilder::new(generate_random_ascii(33)).build().err(), ValidationError::SsidTooLong { bytes: 33 }),
+        (WifiBuilder::new("guest").wpa(generate_random_ascii(7)).build().err(), ValidationError::InvalidWpaPassphrase { bytes: 7, policy: ValidationPolicy::Strict }),
+        (WifiBuilder::new("guest").wep(generate_random_hex(11)).build().err(), ValidationError::InvalidWepKey { bytes: 11, policy: ValidationPolicy::Strict }),
+    ];
+    for (actual, expected) in cases {
+        assert_eq!(actual, Some(expected.clone()), "Expected {:?}", expected);
+    }
+    let open = WifiBuilder::new("guest").wpa(generate_random_ascii(7)).nopass().build().unwrap();
+    assert_eq!(open.password().expose_secret(), None);
+}
+
+#[test]
+fn wifi_builder_validate_all_reports_every_field() {
+    let errors = WifiBuilder::new(generate_random_ascii(33)).wep(generate_random_hex(11)).validate_all();
+    assert_eq!(errors, [ValidationError::SsidTooLong { bytes: 33 }, ValidationError::InvalidWepKey { bytes: 11, policy: ValidationPolicy::Strict }]);
+    assert_eq!(WifiBuilder::new("").wpa(generate_random_ascii(16)).validate_all(), [ValidationError::EmptySsid]);
+    assert!(WifiBuilder::new("guest").wpa(generate_random_ascii(7)).nopass().validate_all().is_empty());
+}
+
+#[test]
+fn wifi_lints_flag_likely_mistakes() {
+    let cases = vec![
+        (WifiBuilder::new("guest").wpa("password").build().unwrap(), vec![]),
+        (WifiBuilder::new(" guest").wpa("password ").build().unwrap(), vec![Lint::SsidWhitespace, Lint::PasswordWhitespace]),
+        (WifiBuilder::new("guest\tnet").build().unwrap(), vec![Lint::SsidControlCharacter]),
+        (WifiBuilder::new("guest").wep("abcde").build().unwrap(), vec![Lint::Wep]),
+        (WifiBuilder::new("guest").wpa("clé🔑secrète").policy(ValidationPolicy::Lenient).build().unwrap(), vec![Lint::PasswordNotAscii]),
+        (Wifi::new(Ssid::from_bytes(b"caf\xe9").unwrap(), Password::new(None, AuthType::Nopass).unwrap(), false), vec![Lint::SsidNotUtf8]),
+    ];
+    for (wifi, expected) in cases {
+        assert_eq!(wifi.lints(), expected, "{:?}", wifi);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn wifi_to_qr_grows_with_the_payload() {
+    let short = WifiBuilder::new("guest").build().unwrap().to_qr(ErrorCorrection::L).unwrap();
+    let long = WifiBuilder::new(generate_random_ascii(32)).wpa(generate_random_ascii(63)).build().unwrap();
+    let dense = long.to_qr(ErrorCorrection::H).unwrap();
+    for qr in [&short, &dense] {
+        assert_eq!(qr.width(), 17 + 4 * qr.version() as usize);
+        assert_eq!(qr.modules().len(), qr.width() * qr.width());
+        // Finder patterns sit in three corners, with a light separator next to each.
+        for (x, y) in [(0, 0), (qr.width() - 1, 0), (0, qr.width() - 1)] {
+            assert!(qr.is_dark(x, y), "{:?}", (x, y));
+        }
+        assert!(!qr.is_dark(7, 7));
+    }
+    assert!(dense.version() > short.version());
+    let mut svg = Vec::new();
+    write_svg(dense.modules(), dense.width(), &RenderOptions::default(), &mut svg).unwrap();
+    assert!(String::from_utf8(svg).unwrap().ends_with("</svg>\n"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn renderers_draw_the_matrix() {
+    let qr = WifiBuilder::new(generate_random_ascii(16)).wpa(generate_random_ascii(16)).build().unwrap().to_qr(ErrorCorrection::M).unwrap();
+    let options = RenderOptions { margin: 2, ..Default::default() };
+    let mut ascii = Vec::new();
+    AsciiRenderer.render(&qr, &options, &mut ascii).unwrap();
+    let ascii = String::from_utf8(ascii).unwrap();
+    // Two rows per line, plus the margin above and below.
+    assert_eq!(ascii.lines().count(), (qr.width() + 4).div_ceil(2));
+    let mut svg = Vec::new();
+    SvgRenderer.render(&qr, &options, &mut svg).unwrap();
+    let mut expected = Vec::new();
+    write_svg(qr.modules(), qr.width(), &options, &mut expected).unwrap();
+    assert_eq!(svg, expected);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn wifi_serde_round_trips_through_toml() {
+    let raw_ssid = generate_random_mbstring(16, &[DoubleByte, TripleByte]);
+    let raw_pass = generate_random_ascii(16);
+    let wifi = WifiBuilder::new(raw_ssid.clone()).wpa(raw_pass.clone()).hidden(true).build().unwrap();
+    let text = toml::to_string(&wifi).unwrap();
+    let parsed: Wifi = toml::from_str(&text).unwrap();
+    assert_eq!(parsed.to_mecard(), wifi.to_mecard());
+    let raw = Wifi::new(Ssid::from_bytes(b"caf\xe9").unwrap(), Password::new(None, AuthType::Nopass).unwrap(), false);
+    let parsed: Wifi = serde_json::from_str(&serde_json::to_string(&raw).unwrap()).unwrap();
+    assert_eq!(parsed.ssid().as_bytes(), b"caf\xe9");
+    let enterprise = Enterprise::new(EapMethod::AkaPrime).with_identity("alice");
+    let eap = WifiBuilder::new("campus").eap(enterprise, None).build().unwrap();
+    let parsed: Wifi = toml::from_str(&toml::to_string(&eap).unwrap()).unwrap();
+    assert_eq!(parsed.to_mecard(), "WIFI:S:campus;T:WPA2-EAP;P:;E:AKA';I:alice;H:false;;");
+    let sae = WifiBuilder::new("lobby").sae("pw").transition_disable(true).build().unwrap();
+    assert_eq!(serde_json::to_string(&sae).unwrap(), r#"{"ssid":"lobby","auth":"SAE","password":"pw","hidden":false,"transition_disable":true}"#);
+    let parsed: Wifi = toml::from_str(&toml::to_string(&sae).unwrap()).unwrap();
+    assert_eq!(parsed.to_mecard(), sae.to_mecard());
+    let open: Wifi = toml::from_str("ssid = \"guest\"\nauth = \"nopass\"\n").unwrap();
+    assert_eq!(open.to_mecard(), "WIFI:S:guest;T:nopass;P:;H:false;;");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn wifi_serde_validates_on_deserialize() {
+    let cases = vec![
+        (r#"{"ssid": ""}"#, "SSID cannot be empty."),
+        (r#"{"ssid": "guest", "auth": "WEP", "password": "1234"}"#, "WEP password must be"),
+        (r#"{"ssid": "guest", "auth": "WPA3"}"#, "unknown variant"),
+        (r#"{"ssid": "guest", "psk": "password"}"#, "unknown field"),
+        (r#"{"ssid": "campus", "auth": "WPA2-EAP"}"#, "WPA2-EAP networks need an EAP method."),
+        (r#"{"ssid": "guest", "password": "password", "identity": "alice"}"#, "EAP settings are only allowed"),
+    ];
+    for (input, expected) in cases {
+        let err = serde_json::from_str::<Wifi>(input).err().unwrap().to_string();
+        assert!(err.contains(expected), "{:?} should fail with {:?}, got {:?}", input, expected, err);
+    }
+}
+
+#[test]
+fn auth_type_parses_aliases_ignoring_case() {
+    let cases = vec![
+        ("WPA", AuthType::Wpa),
+        ("wpa2", AuthType::Wpa),
+        ("Wpa3", AuthType::Wpa),
+        ("WPA2-PSK", AuthType::Wpa),
+        ("wep", AuthType::Wep),
+        ("wpa2-eap", AuthType::Wpa2Eap),
+        ("SAE", AuthType::Sae),
+        ("owe", AuthType::Owe),
+        ("nopass", AuthType::Nopass),
+        ("OPEN", AuthType::Nopass),
+        ("none", AuthType::Nopass),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(input.parse::<AuthType>(), Ok(expected), "{:?} should parse as {:?}", input, expected);
+        assert_eq!(AuthType::try_from(input), Ok(expected), "{:?} should convert to {:?}", input, expected);
+    }
+    assert_eq!("WPA4".parse::<AuthType>(), Err(ParseError::UnknownAuthType("WPA4".to_string())));
+}
+
+#[test]
+fn rgb_parses_hex_colors() {
+    let cases = vec![
+        ("#000000", Rgb(0, 0, 0)),
+        ("ffffff", Rgb(255, 255, 255)),
+        ("#1E90ff", Rgb(0x1e, 0x90, 0xff)),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(input.parse::<Rgb>(), Ok(expected), "{:?} should parse as {:?}", input, expected);
+    }
+}
+
+#[test]
+fn rgb_rejects_malformed_colors() {
+    let cases = vec!["", "#fff", "#12345g", "#1234567", "black", "#+1+2+3"];
+    for input in cases {
+        assert!(input.parse::<Rgb>().is_err(), "{:?} should be rejected", input);
+    }
+}
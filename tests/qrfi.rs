@@ -4,6 +4,7 @@ use common::generate::CharType::*;
 
 use assert_cmd::Command;
 use predicates::prelude::*;
+use sha2::{Digest, Sha256};
 
 fn run_cli_test<T: AsRef<[u8]>>(args: Vec<String>, stdin: Option<String>, expected_success: bool, expected_output: T) {
     let mut cmd = Command::new(env!("CARGO_BIN_EXE_qrfi"));
@@ -38,13 +39,462 @@ macro_rules! qrfi_test {
 
 qrfi_test! {
     qrfi_accepts_help_arg: vec!["--help".into()], None, true, format!("{}", env!("CARGO_PKG_DESCRIPTION")),
+    qrfi_accepts_low_error_correction: vec![format!("--password={}", generate_random_ascii(16)), "-e".into(), "L".into(), "--".into(), generate_random_ascii(16)], None, true, "█",
     qrfi_accepts_nopass_auth_type: vec!["-t".into(), "nopass".into(), format!("--password={}", generate_random_ascii(16)), "--".into(), generate_random_ascii(16)], None, true, "█",
     qrfi_accepts_ssid_via_args: vec![format!("--password={}", generate_random_ascii(16)), "--".into(), generate_random_mbstring(32, &[TripleByte])], None, true, "█",
     qrfi_accepts_ssid_via_stdin: vec![format!("--password={}", generate_random_hex(64))], Some(generate_random_ascii(16)), true, "█",
     qrfi_accepts_version_arg: vec!["--version".into()], None, true, format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+    qrfi_accepts_payload_over_max_version_without_strict: vec![format!("--password={}", generate_random_ascii(63)), "--max-version=1".into(), "--".into(), generate_random_ascii(32)], None, true, "█",
+    qrfi_accepts_hex_ssid_emission: vec![format!("--password={}", generate_random_ascii(16)), "--hex-ssid".into(), "--".into(), generate_random_mbstring(8, &[TripleByte])], None, true, "█",
+    qrfi_compat_report_lists_matching_caveats: vec!["--password=pass;word".into(), "--hidden".into(), "--compat-report".into(), "--".into(), generate_random_ascii(16)], None, true, "Do not unescape backslashes, so the password they try is wrong.",
+    qrfi_accepts_wpa2_eap_settings: vec!["-t".into(), "WPA2-EAP".into(), "--eap=PEAP".into(), "--phase2=MSCHAPV2".into(), "--identity=alice".into(), format!("--password={}", generate_random_ascii(16)), "--".into(), generate_random_ascii(16)], None, true, "█",
+    qrfi_compat_report_suggests_sae_as_wpa: vec!["-t".into(), "SAE".into(), "--password=pw".into(), "--compat-report".into(), "--".into(), generate_random_ascii(16)], None, true, "--sae-as-wpa labels the network WPA",
+    qrfi_accepts_wifi_uri_dialect: vec![format!("--password={}", generate_random_ascii(16)), "--dialect=wifi-uri".into(), "--".into(), generate_random_ascii(16)], None, true, "█",
+    qrfi_accepts_utf8_passphrase_with_lenient_validation: vec!["--password=Passwört!".into(), "--validation=lenient".into(), "--".into(), generate_random_ascii(16)], None, true, "█",
+    qrfi_accepts_256_bit_wep_key_with_lenient_validation: vec!["-t".into(), "WEP".into(), format!("--password={}", generate_random_ascii(29)), "--validation=lenient".into(), "--".into(), generate_random_ascii(16)], None, true, "█",
+    qrfi_accepts_empty_ssid_without_validation: vec!["--password=x".into(), "--validation=off".into(), "--".into(), "".into()], None, true, "█",
+    qrfi_accepts_owe_alias: vec!["-t".into(), "owe".into(), "--".into(), generate_random_ascii(16)], None, true, "█",
+    qrfi_accepts_auth_type_aliases_like_the_wizard: vec!["-t".into(), "WPA3-SAE".into(), "--password=pw".into(), "--compat-report".into(), "--".into(), generate_random_ascii(16)], None, true, "--sae-as-wpa labels the network WPA",
+    qrfi_accepts_open_auth_type_alias: vec!["-t".into(), "open".into(), "--".into(), generate_random_ascii(16)], None, true, "█",
+    qrfi_compat_report_without_caveats: vec!["--password=password".into(), "--compat-report".into(), "--".into(), "guest".into()], None, true, "No known reader caveats.",
+    qrfi_accepts_ssid_as_hex: vec![format!("--password={}", generate_random_ascii(16)), "--ssid-hex=636166e9".into()], None, true, "█",
+    qrfi_outputs_escpos_format: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "escpos".into(), "--".into(), generate_random_ascii(16)], None, true, &b"\x1b@\x1ba\x01\x1dv0\x00"[..],
+    qrfi_outputs_gif_format: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "gif".into(), "--".into(), generate_random_ascii(16)], None, true, &b"GIF89a"[..],
+    qrfi_outputs_animated_gif_format: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "gif-animated".into(), "--".into(), generate_random_ascii(16)], None, true, &b"NETSCAPE2.0"[..],
+    qrfi_outputs_png_with_size_preset: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "png".into(), "--preset=favicon".into(), "--".into(), generate_random_ascii(16)], None, true, &b"\x89PNG"[..],
+    qrfi_outputs_outlined_svg: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "svg".into(), "--outline".into(), "--".into(), generate_random_ascii(16)], None, true, "fill-rule=\"evenodd\"",
     qrfi_outputs_png_format: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "png".into(), "--".into(), generate_random_mbstring(32, &[DoubleByte])], None, true, &b"\x89PNG"[..],
+    qrfi_outputs_png_for_ptouch_tape: vec![format!("--password={}", generate_random_ascii(8)), "-f".into(), "png".into(), "--tape=24mm".into(), "--margin=2".into(), "--".into(), generate_random_ascii(8)], None, true, &b"\x89PNG"[..],
     qrfi_outputs_svg_format: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "svg".into(), "--".into(), generate_random_mbstring(32, &[QuadrupleByte])], None, true, "<svg",
+    qrfi_outputs_svg_with_custom_colors: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "svg".into(), "--dark-color=#1e90ff".into(), "--".into(), generate_random_ascii(16)], None, true, "fill=\"#1e90ff\"",
+    qrfi_outputs_svgz_format: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "svgz".into(), "--".into(), generate_random_ascii(16)], None, true, &b"\x1f\x8b"[..],
+    qrfi_outputs_svg_with_label: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "svg".into(), "--margin=6".into(), "--label=Guest & Co".into(), "--".into(), generate_random_ascii(16)], None, true, ">Guest &amp; Co</text></svg>",
+    qrfi_outputs_svg_with_watermark: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "svg".into(), "--watermark=INTERNAL <DRAFT>".into(), "--".into(), generate_random_ascii(16)], None, true, ">INTERNAL &lt;DRAFT&gt;</text>",
+    qrfi_outputs_zpl_format: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "zpl".into(), "--".into(), generate_random_ascii(16)], None, true, "^FO0,0^GFA,",
+    qrfi_outputs_zpl_centered_on_label: vec!["--password=password1".into(), "-f".into(), "zpl".into(), "--label-width=100mm".into(), "--dpi=203".into(), "--".into(), "guest".into()], None, true, "^PW799\n^LL777\n^FO11,0^GFA,",
+    qrfi_passes_compliance_report: vec![format!("--password={}", generate_random_ascii(16)), "--compliance-report".into(), "--".into(), generate_random_ascii(16)], None, true, "Result: PASS",
+    qrfi_prints_scanability_score: vec![format!("--password={}", generate_random_ascii(16)), "--score".into(), "--".into(), generate_random_ascii(16)], None, true, "Scanability score: ",
+    qrfi_rejects_invalid_password_in_check_mode: vec![format!("--password={}", generate_random_ascii(7)), "--check".into(), "--".into(), generate_random_ascii(16)], None, false, "WPA passphrase must be",
+    qrfi_rejects_oversized_payload_in_check_mode: vec!["-t".into(), "SAE".into(), format!("--password={}", generate_random_hex(3000)), "--check".into(), "--".into(), generate_random_ascii(16)], None, false, "too long for a QR code",
+    qrfi_rejects_ptouch_tape_too_narrow_in_check_mode: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "png".into(), "--tape=9mm".into(), "--margin=40".into(), "--check".into(), "--".into(), generate_random_ascii(16)], None, false, "does not fit",
+    qrfi_rejects_watermark_for_raster_format: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "png".into(), "--watermark=DRAFT".into(), "--".into(), generate_random_ascii(16)], None, false, "--watermark requires --format svg",
+    qrfi_rejects_watermark_for_bundle: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "svg".into(), "--watermark=DRAFT".into(), "--bundle=qrfi-bundle-unused".into(), "--".into(), generate_random_ascii(16)], None, false, "--watermark cannot be used with --bundle",
+    qrfi_rejects_outline_in_strict_mode: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "png".into(), "--outline".into(), "--strict".into(), "--".into(), generate_random_ascii(16)], None, false, "scan less reliably",
+    qrfi_rejects_label_without_room_for_quiet_zone: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "svg".into(), "--label=Guest".into(), "--".into(), generate_random_ascii(16)], None, false, "--label needs a margin of at least 6 modules",
+    qrfi_rejects_malformed_ssid_hex: vec![format!("--password={}", generate_random_ascii(16)), "--ssid-hex=63616".into()], None, false, "is not an even number of hex digits",
+    qrfi_rejects_semicolon_in_tasmota_export: vec!["--password=pass;word".into(), "--export=tasmota".into(), "--".into(), generate_random_ascii(16)], None, false, "--export tasmota cannot express",
+    qrfi_rejects_quote_in_ssid_for_netsh_export: vec![format!("--password={}", generate_random_ascii(16)), "--export=netsh".into(), "--".into(), r#"a" & calc & "b"#.into()], None, false, "--export netsh cannot express an SSID containing",
+    qrfi_rejects_emit_psk_for_wep: vec!["--password=12345".into(), "-t".into(), "WEP".into(), "--emit-psk".into(), "--".into(), generate_random_ascii(16)], None, false, "--emit-psk requires a WPA passphrase",
+    qrfi_rejects_stdin_over_max_input_bytes: vec![format!("--password={}", generate_random_ascii(16)), "--max-input-bytes=64".into()], Some(generate_random_ascii(65)), false, "input is larger than 64 bytes",
+    qrfi_rejects_wpa2_eap_without_method: vec!["-t".into(), "WPA2-EAP".into(), format!("--password={}", generate_random_ascii(16)), "--".into(), generate_random_ascii(16)], None, false, "requires --eap",
+    qrfi_rejects_transition_disable_for_wep: vec!["-t".into(), "WEP".into(), "--password=12345".into(), "--transition-disable".into(), "--".into(), generate_random_ascii(16)], None, false, "--transition-disable requires",
+    qrfi_rejects_password_for_owe: vec!["-t".into(), "OWE".into(), format!("--password={}", generate_random_ascii(16)), "--".into(), generate_random_ascii(16)], None, false, "Password should not be provided for open networks",
+    qrfi_rejects_compat_profile_for_wifi_uri: vec!["--password=password".into(), "--compat=ios".into(), "--dialect=wifi-uri".into(), "--".into(), "guest".into()], None, false, "--compat requires --dialect mecard",
+    qrfi_rejects_hex_ssid_for_wifi_uri: vec![format!("--password={}", generate_random_ascii(16)), "--dialect=wifi-uri".into(), "--hex-ssid".into(), "--".into(), generate_random_ascii(16)], None, false, "--hex-ssid requires --dialect mecard",
+    qrfi_rejects_utf8_passphrase_by_default: vec!["--password=Passwört!".into(), "--".into(), generate_random_ascii(16)], None, false, "WPA passphrase must be 8-63 printable ASCII characters",
+    qrfi_rejects_eap_for_wpa: vec!["--eap=TLS".into(), format!("--password={}", generate_random_ascii(16)), "--".into(), generate_random_ascii(16)], None, false, "--eap requires --authentication-type WPA2-EAP",
     qrfi_rejects_invalid_ssid: vec![format!("--password={}", generate_random_ascii(16)), "--".into(), generate_random_ascii(33)], None, false, "SSID is too long",
+    qrfi_rejects_noncompliant_module_size: vec![format!("--password={}", generate_random_ascii(16)), "--compliance-report".into(), "--scan-distance=3m".into(), "--".into(), generate_random_ascii(16)], None, false, "does not meet QR print guidelines",
+    qrfi_rejects_payload_over_max_version_in_porcelain_mode: vec![format!("--password={}", generate_random_ascii(63)), "--max-version=1".into(), "--strict".into(), "--porcelain".into(), "--".into(), generate_random_ascii(32)], None, false, "qrfi:error:max-version:Payload needs QR version",
+    qrfi_rejects_payload_over_max_version_in_strict_mode: vec![format!("--password={}", generate_random_ascii(63)), "--max-version=1".into(), "--strict".into(), "--".into(), generate_random_ascii(32)], None, false, "exceeds the maximum of 1",
+    qrfi_rejects_too_small_output_for_scan_distance_in_strict_mode: vec![format!("--password={}", generate_random_ascii(16)), "--scan-distance=3m".into(), "--strict".into(), "--".into(), generate_random_ascii(16)], None, false, "too small to scan from 3000 mm",
+    qrfi_rejects_output_too_small_at_any_dpi_in_strict_mode: vec!["--password=password1".into(), "--scan-distance=1000m".into(), "--strict".into(), "--".into(), "guest".into()], None, false, "raise --scale to 40728 or more, or scan from closer",
+    qrfi_rejects_preset_with_explicit_scale: vec![format!("--password={}", generate_random_ascii(16)), "--preset=poster-a4".into(), "--scale=3".into(), "--".into(), generate_random_ascii(16)], None, false, "cannot be used with",
+    qrfi_rejects_zpl_label_too_narrow_for_code: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "zpl".into(), "--label-width=2mm".into(), "--".into(), generate_random_ascii(16)], None, false, "does not fit the 23 dots of the label at 300 dpi",
+    qrfi_rejects_ptouch_tape_too_narrow_for_code: vec![format!("--password={}", generate_random_ascii(63)), "-f".into(), "png".into(), "--tape=9mm".into(), "--".into(), generate_random_ascii(32)], None, false, "does not fit",
+    qrfi_rejects_huge_margin_for_png: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "png".into(), "--margin=4000000000".into(), "--".into(), generate_random_ascii(16)], None, false, "larger than the maximum image size of 16384 pixels",
+    qrfi_rejects_huge_scale_for_png: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "png".into(), "--scale=4000000000".into(), "--".into(), generate_random_ascii(16)], None, false, "larger than the maximum image size of 16384 pixels",
+    qrfi_rejects_huge_margin_for_gif: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "gif".into(), "--margin=100000".into(), "--".into(), generate_random_ascii(16)], None, false, "larger than the maximum image size of 16384 pixels",
     qrfi_rejects_unsupported_jpeg_format: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "jpeg".into(), "--".into(), generate_random_ascii(16)], None, false, "invalid value 'jpeg' for '--format <FORMAT>'",
     qrfi_rejects_unsupported_jpg_format: vec![format!("--password={}", generate_random_ascii(16)), "-f".into(), "jpg".into(), "--".into(), generate_random_ascii(16)], None, false, "invalid value 'jpg' for '--format <FORMAT>'",
 }
+
+#[test]
+fn qrfi_writes_sha256_sidecar_next_to_output() {
+    let dir = std::env::temp_dir().join(format!("qrfi-{}", generate_random_hex(16)));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = dir.join("qr.png");
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-f", "png", "--checksum", "sha256", "-p", &generate_random_hex(64), "-o"])
+        .arg(&output)
+        .args(["--", &generate_random_ascii(16)])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+    let sidecar = std::fs::read_to_string(dir.join("qr.png.sha256")).unwrap();
+    let image = std::fs::read(&output).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let (hash, name) = sidecar.split_once("  ").unwrap();
+    let expected: String = Sha256::digest(&image).iter().map(|byte| format!("{:02x}", byte)).collect();
+    assert_eq!(hash, expected, "Sidecar should hold the SHA-256 of the image: {:?}", sidecar);
+    assert_eq!(name, "qr.png\n");
+}
+
+#[test]
+fn qrfi_writes_bundle_directory() {
+    let dir = std::env::temp_dir().join(format!("qrfi-{}", generate_random_hex(16)));
+    let ssid = generate_random_mbstring(16, &[TripleByte]);
+    let password = generate_random_hex(64);
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-p", &password, "--bundle"])
+        .arg(&dir)
+        .args(["--", &ssid])
+        .assert()
+        .success();
+    let png = std::fs::read(dir.join("qr.png")).unwrap();
+    let svg = std::fs::read_to_string(dir.join("qr.svg")).unwrap();
+    let alt = std::fs::read_to_string(dir.join("alt.txt")).unwrap();
+    let credentials = std::fs::read_to_string(dir.join("credentials.txt")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(png.starts_with(b"\x89PNG"));
+    assert!(svg.contains("<svg"));
+    assert!(alt.contains(&ssid));
+    assert!(credentials.contains(&format!("Network:  {}\n", ssid)));
+    assert!(credentials.contains(&format!("Password: {}\n", password)));
+}
+
+#[test]
+fn qrfi_redacted_bundle_hides_code_and_password() {
+    let dir = std::env::temp_dir().join(format!("qrfi-{}", generate_random_hex(16)));
+    let ssid = generate_random_ascii(16);
+    let password = generate_random_hex(64);
+    let plain = Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-p", &password, "-f", "svg", "--", &ssid])
+        .output()
+        .unwrap();
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-p", &password, "--redacted", "--bundle"])
+        .arg(&dir)
+        .args(["--", &ssid])
+        .assert()
+        .success();
+    let svg = std::fs::read(dir.join("qr.svg")).unwrap();
+    let credentials = std::fs::read_to_string(dir.join("credentials.txt")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(svg.starts_with(b"<?xml"));
+    assert_ne!(svg, plain.stdout);
+    assert!(credentials.contains("Password: ********\n"));
+    assert!(!credentials.contains(&password));
+}
+
+#[test]
+fn qrfi_writes_color_variants() {
+    let dir = std::env::temp_dir().join(format!("qrfi-{}", generate_random_hex(16)));
+    std::fs::create_dir_all(&dir).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-p", &generate_random_hex(64), "-f", "svg", "--dark-color=#1e90ff", "--variants", "light,dark,high-contrast", "-o"])
+        .arg(dir.join("qr.svg"))
+        .args(["--", &generate_random_ascii(16)])
+        .assert()
+        .success();
+    let light = std::fs::read_to_string(dir.join("qr-light.svg")).unwrap();
+    let dark = std::fs::read_to_string(dir.join("qr-dark.svg")).unwrap();
+    let high_contrast = std::fs::read_to_string(dir.join("qr-high-contrast.svg")).unwrap();
+    let plain_exists = dir.join("qr.svg").exists();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(!plain_exists);
+    assert!(light.contains(r##"fill="#ffffff"/><path fill="#1e90ff""##));
+    assert!(dark.contains(r##"fill="#1e90ff"/><path fill="#ffffff""##));
+    assert!(high_contrast.contains(r##"fill="#ffffff"/><path fill="#000000""##));
+}
+
+#[cfg(feature = "avif")]
+#[test]
+fn qrfi_outputs_avif_format() {
+    let output = Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-p", &generate_random_hex(64), "-f", "avif", "--", &generate_random_ascii(16)])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(&output.stdout[4..12], b"ftypavif");
+}
+
+#[test]
+fn qrfi_reports_every_invalid_field() {
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args([format!("--password={}", generate_random_ascii(7)), "--".into(), generate_random_ascii(33)])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("SSID is too long"))
+        .stderr(predicate::str::contains("WPA passphrase must be"));
+}
+
+#[test]
+fn qrfi_emit_psk_encodes_derived_key() {
+    // The PSK test vector from IEEE 802.11i Annex H.4.
+    let psk = "f42c6fc52df0ebef9ebb4b90b38a5f902e83fe1b135a70e23aed762e9710a12e";
+    let derived = Command::new(env!("CARGO_BIN_EXE_qrfi")).args(["-p", "password", "--emit-psk", "--", "IEEE"]).output().unwrap();
+    let direct = Command::new(env!("CARGO_BIN_EXE_qrfi")).args(["-p", psk, "--", "IEEE"]).output().unwrap();
+    assert!(derived.status.success());
+    assert_eq!(derived.stdout, direct.stdout);
+}
+
+#[test]
+fn qrfi_emit_psk_keeps_passphrase_out_of_exports_and_bundle() {
+    let psk = "f42c6fc52df0ebef9ebb4b90b38a5f902e83fe1b135a70e23aed762e9710a12e";
+    let dir = std::env::temp_dir().join(format!("qrfi-{}", generate_random_hex(16)));
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-p", "password", "--emit-psk", "--export", "nmcli,netsh,networksetup", "--export-dir"])
+        .arg(&dir)
+        .arg("--bundle")
+        .arg(dir.join("bundle"))
+        .args(["--", "IEEE"])
+        .assert()
+        .success();
+    let files = ["wifi-nmcli.sh", "wifi-netsh.xml", "wifi-networksetup.sh", "bundle/credentials.txt"];
+    let contents: Vec<String> = files.iter().map(|name| std::fs::read_to_string(dir.join(name)).unwrap()).collect();
+    std::fs::remove_dir_all(&dir).unwrap();
+    for (name, text) in files.iter().zip(&contents) {
+        assert!(text.contains(psk), "{} should hold the derived key: {:?}", name, text);
+        assert!(!text.contains("password"), "{} must not hold the passphrase: {:?}", name, text);
+    }
+}
+
+#[test]
+fn qrfi_prints_lints_without_failing_in_strict_mode() {
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["--strict", "--porcelain", "-t", "WEP", "-p", "abcde", "--", "Guest "])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("█"))
+        .stderr(predicate::str::contains("qrfi:warning:ssid-whitespace:"))
+        .stderr(predicate::str::contains("qrfi:warning:wep:"));
+}
+
+#[test]
+fn qrfi_explain_describes_each_field_without_the_password() {
+    let password = format!("{};", generate_random_hex(16));
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["--check", "--explain", "--hidden", "-p", &password, "--", "cafe"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(r#"S:"cafe" is the SSID "cafe", quoted because"#))
+        .stderr(predicate::str::contains("T:WPA means"))
+        .stderr(predicate::str::contains("P:******** is the 17-character passphrase (masked here), with 1 backslash escape(s)"))
+        .stderr(predicate::str::contains("H:true tells the device to probe"))
+        .stderr(predicate::str::contains(password).not());
+}
+
+#[test]
+fn qrfi_explain_follows_the_compat_profile() {
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["--check", "--explain", "--compat=ios", "-t", "nopass", "--", "guest"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("T:nopass means an open network without a password.\nnote: S:guest"))
+        .stderr(predicate::str::contains("P: is left out because the password is empty (--compat ios)."))
+        .stderr(predicate::str::contains("H: is left out because the network broadcasts its SSID"));
+}
+
+#[test]
+fn qrfi_prompt_snippet_keeps_options_but_not_the_network() {
+    let password = generate_random_ascii(16);
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["--prompt-snippet=bash", "-f", "png", "--margin=2", "--watermark=it's a draft", &format!("--password={}", password), "--", "lobby"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#"qrfi --format=png --margin=2 '--watermark=it'\''s a draft' "$@""#))
+        .stdout(predicate::str::contains("lobby").not())
+        .stdout(predicate::str::contains(password).not());
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["--prompt-snippet=fish", "--hidden"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("function wifi-qr\n    qrfi --hidden $argv\nend"));
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["--prompt-snippet=bash", "-t", "WPA2-EAP", "--eap=PEAP", "--identity=alice", "-o", "lobby.png", "--check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#"qrfi --authentication-type=WPA2-EAP --eap=PEAP "$@""#))
+        .stdout(predicate::str::contains("alice").not())
+        .stdout(predicate::str::contains("lobby.png").not());
+}
+
+#[test]
+fn qrfi_check_mode_validates_without_output() {
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["--check", "-p", &generate_random_hex(64), "--", &generate_random_ascii(16)])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn qrfi_interactive_mode_asks_again_after_invalid_answers() {
+    let stdin = format!("{}\nwpa4\nwpa2\n{}\n{}\nmaybe\ny\n", generate_random_ascii(16), generate_random_ascii(7), generate_random_hex(64));
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .arg("--interactive")
+        .write_stdin(stdin)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("█"))
+        .stderr(predicate::str::contains(r#"Unknown authentication type "wpa4"."#))
+        .stderr(predicate::str::contains("WPA passphrase must be"))
+        .stderr(predicate::str::contains("Please answer y or n."));
+}
+
+#[test]
+fn qrfi_interactive_mode_fails_on_incomplete_input() {
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .arg("--interactive")
+        .write_stdin(format!("{}\n", generate_random_ascii(16)))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Input ended before all questions were answered."));
+}
+
+#[test]
+fn qrfi_interactive_mode_limits_answers_to_max_input_bytes() {
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["--interactive", "--max-input-bytes=8"])
+        .write_stdin(format!("{}\n", generate_random_hex(16)))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Answer is longer than 8 bytes"));
+}
+
+#[test]
+fn qrfi_enforces_policy_file_with_specific_exit_codes() {
+    let dir = std::env::temp_dir().join(format!("qrfi-{}", generate_random_hex(16)));
+    std::fs::create_dir_all(&dir).unwrap();
+    let policy = dir.join("policy.toml");
+    std::fs::write(&policy, concat!(
+        "[ssid]\ndeny = [\"corp-prod\"]\n",
+        "[password]\nmin_entropy_bits = 60\n",
+        "[output]\nmin_error_correction = \"Q\"\n",
+    )).unwrap();
+    let cases = vec![
+        (vec!["-e", "Q", "-p", "correct horse battery", "--", "corp-prod"], 10, "Policy forbids encoding the SSID"),
+        (vec!["-e", "Q", "-p", "password", "--", "guest"], 11, "bits of entropy"),
+        (vec!["-e", "M", "-p", "correct horse battery", "--", "guest"], 12, "error correction level Q"),
+    ];
+    for (args, code, message) in cases {
+        // --check must fail exactly where a real run would.
+        for check in [None, Some("--check")] {
+            Command::new(env!("CARGO_BIN_EXE_qrfi"))
+                .arg("--policy")
+                .arg(&policy)
+                .args(check)
+                .args(&args)
+                .assert()
+                .code(code)
+                .stderr(predicate::str::contains(message));
+        }
+    }
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .arg("--policy")
+        .arg(&policy)
+        .args(["-e", "H", "-p", "correct horse battery", "--", "guest"])
+        .assert()
+        .success();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn qrfi_appends_audit_log_without_password() {
+    let dir = std::env::temp_dir().join(format!("qrfi-{}", generate_random_hex(16)));
+    std::fs::create_dir_all(&dir).unwrap();
+    let log = dir.join("audit.log");
+    let password = generate_random_hex(64);
+    for _ in 0..2 {
+        Command::new(env!("CARGO_BIN_EXE_qrfi"))
+            .args(["-p", &password, "--audit-log"])
+            .arg(&log)
+            .args(["--", "AuditedSSID"])
+            .assert()
+            .success();
+    }
+    let lines = std::fs::read_to_string(&log).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(lines.lines().count(), 2, "Each run should append one line: {:?}", lines);
+    for line in lines.lines() {
+        assert!(line.contains(r#""ssid":"AuditedSSID""#), "Line should record the SSID: {:?}", line);
+        assert!(line.contains(r#""sha256":null"#), "Line must not record a hash of the code: {:?}", line);
+        assert!(!line.contains(&password), "Line must not contain the password: {:?}", line);
+    }
+}
+
+#[test]
+fn qrfi_keeps_hashes_of_secured_artifacts_out_of_audit_log() {
+    let dir = std::env::temp_dir().join(format!("qrfi-{}", generate_random_hex(16)));
+    let log = dir.join("audit.log");
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-p", &generate_random_hex(16), "--export", "nmcli", "--export-dir"])
+        .arg(&dir)
+        .arg("--bundle")
+        .arg(dir.join("bundle"))
+        .arg("--audit-log")
+        .arg(&log)
+        .args(["--", "AuditedSSID"])
+        .assert()
+        .success();
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-t", "nopass", "-o"])
+        .arg(dir.join("open.txt"))
+        .arg("--audit-log")
+        .arg(&log)
+        .args(["--", "OpenSSID"])
+        .assert()
+        .success();
+    let lines = std::fs::read_to_string(&log).unwrap();
+    let digests: Vec<String> = ["bundle/qr.png", "bundle/qr.svg", "open.txt"].iter()
+        .map(|name| Sha256::digest(std::fs::read(dir.join(name)).unwrap()).iter().map(|byte| format!("{:02x}", byte)).collect())
+        .collect();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(lines.lines().count(), 6, "{:?}", lines);
+    for line in lines.lines() {
+        // Only the alt text and the code of the open network carry nothing derived from a password.
+        let secret = !line.contains("alt.txt") && !line.contains("OpenSSID");
+        assert_eq!(line.contains(r#""sha256":null"#), secret, "{:?}", line);
+    }
+    assert!(!lines.contains(&digests[0]) && !lines.contains(&digests[1]), "{:?}", lines);
+    assert!(lines.contains(&digests[2]), "{:?}", lines);
+}
+
+#[test]
+fn qrfi_exports_provisioning_scripts() {
+    let dir = std::env::temp_dir().join(format!("qrfi-{}", generate_random_hex(16)));
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-p", "it's a secret", "--export", "nmcli,netsh,networksetup", "--export-dir"])
+        .arg(&dir)
+        .args(["--", "Guest & Co"])
+        .assert()
+        .success();
+    let nmcli = std::fs::read_to_string(dir.join("wifi-nmcli.sh")).unwrap();
+    let profile = std::fs::read_to_string(dir.join("wifi-netsh.xml")).unwrap();
+    let netsh = std::fs::read_to_string(dir.join("wifi-netsh.cmd")).unwrap();
+    let networksetup = std::fs::read_to_string(dir.join("wifi-networksetup.sh")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(nmcli.contains(r"ssid 'Guest & Co' 802-11-wireless.hidden no wifi-sec.key-mgmt wpa-psk wifi-sec.psk 'it'\''s a secret'"), "{}", nmcli);
+    assert!(profile.contains("<name>Guest &amp; Co</name>"), "{}", profile);
+    assert!(profile.contains("<keyMaterial>it's a secret</keyMaterial>"), "{}", profile);
+    assert!(netsh.contains("netsh wlan connect name=\"Guest & Co\""), "{}", netsh);
+    assert!(networksetup.contains(r"'Guest & Co' 0 WPA2 'it'\''s a secret'"), "{}", networksetup);
+
+    let dir = std::env::temp_dir().join(format!("qrfi-{}", generate_random_hex(16)));
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-t", "WEP", "-p", "abcde", "--export", "nmcli", "--export-dir"])
+        .arg(&dir)
+        .args(["--", "Guest & Co"])
+        .assert()
+        .success();
+    let nmcli = std::fs::read_to_string(dir.join("wifi-nmcli.sh")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(nmcli.contains("wifi-sec.key-mgmt none wifi-sec.wep-key-type 1 wifi-sec.wep-key0 'abcde'"), "{}", nmcli);
+}
+
+#[test]
+fn qrfi_exports_smart_home_configs() {
+    let dir = std::env::temp_dir().join(format!("qrfi-{}", generate_random_hex(16)));
+    Command::new(env!("CARGO_BIN_EXE_qrfi"))
+        .args(["-p", r#"say "hi" \o/"#, "--hidden", "--export", "esphome,tasmota", "--export-dir"])
+        .arg(&dir)
+        .args(["--", "Guest Net"])
+        .assert()
+        .success();
+    let esphome = std::fs::read_to_string(dir.join("wifi-esphome.yaml")).unwrap();
+    let tasmota = std::fs::read_to_string(dir.join("wifi-tasmota.txt")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(esphome, "wifi:\n  ssid: \"Guest Net\"\n  password: \"say \\\"hi\\\" \\\\o/\"\n  fast_connect: true\n");
+    assert_eq!(tasmota, "Backlog SSId1 Guest Net; Password1 say \"hi\" \\o/\n");
+}